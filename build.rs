@@ -0,0 +1,70 @@
+//! Compiles the solutions in `src/bin` into the runner binary, see `src/runner/main.rs`.
+//!
+//! Every solution binary named `DD.rs` or `YYYY-DD.rs` that uses the `solution!` macro is included as a module,
+//! and registered with the generated `registry()` function so `all` and `time` can run it.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut solutions: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+                .filter_map(|path| {
+                    let name = path.file_stem()?.to_str()?.to_string();
+                    let source = fs::read_to_string(&path).ok()?;
+                    (is_solution_name(&name) && source.contains("solution!("))
+                        .then(|| (name, path.to_string_lossy().into_owned()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    solutions.sort();
+
+    let mut out = String::from("// Generated by `build.rs` from the solutions in `src/bin`.\n");
+
+    for (name, path) in &solutions {
+        out += &format!(
+            "\n#[allow(dead_code)]\n#[path = {path:?}]\nmod {};\n",
+            module_name(name)
+        );
+    }
+
+    out += "\n/// All solutions in `src/bin`.\npub fn registry() -> advent_of_code::template::registry::Registry {\n";
+    out += "    use advent_of_code::template::{registry::{Registry, Solution}, PuzzleId};\n\n";
+    out += "    Registry::new(vec![\n";
+
+    for (name, _) in &solutions {
+        let module = module_name(name);
+        out += &format!(
+            "        Solution {{ puzzle: PuzzleId::from_bin_name({name:?}, {module}::DAY), solve: {module}::__solve }},\n"
+        );
+    }
+
+    out += "    ])\n}\n";
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+}
+
+/// Solution binaries are named `DD` or `YYYY-DD`.
+fn is_solution_name(name: &str) -> bool {
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+
+    match name.split_once('-') {
+        Some((year, day)) => year.len() == 4 && is_digits(year) && day.len() == 2 && is_digits(day),
+        None => name.len() == 2 && is_digits(name),
+    }
+}
+
+fn module_name(name: &str) -> String {
+    format!("solution_{}", name.replace('-', "_"))
+}
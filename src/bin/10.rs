@@ -1,20 +1,24 @@
-use core::slice;
+use advent_of_code::{
+    geom::{Dir4, Point2},
+    grid::{Grid, Pos},
+    polygon,
+};
 
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, parse = parse);
 
-#[derive(Debug,Clone)]
+#[derive(Debug, Clone)]
 enum NodeState {
     Pipe(Directions),
     Ground,
-    Animal
+    Animal,
 }
 
-#[derive(Debug,Clone)]
+#[derive(Debug, Clone)]
 struct Directions {
     north: bool,
     east: bool,
     south: bool,
-    west: bool
+    west: bool,
 }
 
 impl Directions {
@@ -23,390 +27,142 @@ impl Directions {
             north,
             east,
             south,
-            west
+            west,
         }
     }
 
-    fn has(&self, dir: Direction) -> bool {
+    fn has(&self, dir: Dir4) -> bool {
         match dir {
-            Direction::NORTH => self.north,
-            Direction::EAST => self.east,
-            Direction::SOUTH => self.south,
-            Direction::WEST => self.west
+            Dir4::North => self.north,
+            Dir4::East => self.east,
+            Dir4::South => self.south,
+            Dir4::West => self.west,
         }
     }
 
-    fn get_dir_vector(&self) -> Vec<Direction> {
+    fn get_dir_vector(&self) -> Vec<Dir4> {
         let mut vec = Vec::new();
 
-        if self.north { vec.push(Direction::NORTH) }
-        if self.east { vec.push(Direction::EAST) }
-        if self.south { vec.push(Direction::SOUTH) }
-        if self.west { vec.push(Direction::WEST) }
+        if self.north {
+            vec.push(Dir4::North)
+        }
+        if self.east {
+            vec.push(Dir4::East)
+        }
+        if self.south {
+            vec.push(Dir4::South)
+        }
+        if self.west {
+            vec.push(Dir4::West)
+        }
 
         vec
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-enum Direction {
-    NORTH,
-    EAST,
-    SOUTH,
-    WEST
+pub struct Map {
+    /// The position of the animal, [`None`] if the input has no `S`.
+    start_position: Option<Pos>,
+    nodes: Grid<NodeState>,
 }
 
-impl Direction {
-    fn get_opposite(&self) -> Direction {
-        match self {
-            Self::NORTH => Self::SOUTH,
-            Self::EAST => Self::WEST,
-            Self::WEST => Self::EAST,
-            Self::SOUTH => Self:: NORTH
-        }
+impl Map {
+    /// Whether the neighbour of a position in a direction is a pipe connecting back to it.
+    fn is_connected(&self, position: Pos, dir: Dir4) -> bool {
+        matches!(
+            self.nodes.step(position, dir).and_then(|next| self.nodes.get(next)),
+            Some(NodeState::Pipe(pipe_dir)) if pipe_dir.has(dir.opposite())
+        )
     }
 
-    fn get_next_coords(&self, x: usize, y: usize) -> (usize, usize) {
-        match self {
-            Direction::NORTH => (x, y.wrapping_sub(1)),
-            Direction::EAST => (x + 1, y),
-            Direction::SOUTH => (x, y + 1),
-            Direction::WEST => (x.wrapping_sub(1), y)
-        }
+    /// Positions of the pipes connected to a position.
+    fn connections(&self, position: Pos) -> Vec<Pos> {
+        let dirs = match &self.nodes[position] {
+            NodeState::Pipe(pipe_dirs) => pipe_dirs.get_dir_vector(),
+            NodeState::Animal => Dir4::ALL.to_vec(),
+            NodeState::Ground => vec![],
+        };
+
+        dirs.into_iter()
+            .filter(|dir| self.is_connected(position, *dir))
+            .filter_map(|dir| self.nodes.step(position, dir))
+            .collect()
     }
-}
-
-struct Map {
-    size: (usize, usize),
-    start_position: (usize, usize),
-    nodes: Vec<NodeState>
-}
 
-impl Map {
-    fn width(&self) -> usize { self.size.0 }
-    fn height(&self) -> usize { self.size.1 }
-
-    fn get_node(&self, x: usize, y:usize) -> Option<&NodeState> {
-        if x >= self.width() || y >= self.height() {
-            return None;
-        }
-
-        self.nodes.get(x + (y * self.width()))
+    /// The positions of the main loop in order, starting at the animal.
+    /// Pipes next to the animal that lead to a dead end are skipped.
+    fn main_loop(&self) -> Option<Vec<Pos>> {
+        let start = self.start_position?;
+        self.connections(start)
+            .into_iter()
+            .find_map(|first| self.walk_from_start(start, first))
     }
 
-    fn is_pipe_pointing(&self, x: usize, y: usize, dir: Direction) -> bool {
-        let node = self.get_node(x, y);
-        if node.is_none() {
-            false
-        } else {
-            match node.unwrap() {
-                NodeState::Pipe(pipe_dir) => {
-                    pipe_dir.has(dir)
-                },
-                _ => false
-            }
-        }
-
-    }
+    /// Follows the pipes from a neighbour of the animal, the path if it leads back to the animal.
+    fn walk_from_start(&self, start: Pos, first: Pos) -> Option<Vec<Pos>> {
+        let mut path = vec![start, first];
 
-    fn get_node_symbol(&self, position: (usize, usize)) -> &str {
-        match self.get_node(position.0, position.1) {
-            None => "?",
-            Some(node_state) => {
-                match node_state {
-                    NodeState::Ground => ".",
-                    NodeState::Animal => "S",
-                    NodeState::Pipe(pipe_dir) => {
-                        match (pipe_dir.north, pipe_dir.east, pipe_dir.south, pipe_dir.west) {
-                            (true, true, false, false) => "L",
-                            (true, false, true, false) => "|",
-                            (true, false, false, true) => "J",
-                            (false, true, true, false) => "F",
-                            (false, true, false, true) => "-",
-                            (false, false, true, true) => "7",
-                            _ => "?"
-                        }
-                    }
-                }
+        loop {
+            let (previous, current) = (path[path.len() - 2], path[path.len() - 1]);
+            let NodeState::Pipe(pipe_dirs) = &self.nodes[current] else {
+                return None;
+            };
+            // pipes don't connect back to the animal, so check where the pipe points instead.
+            let next = pipe_dirs
+                .get_dir_vector()
+                .into_iter()
+                .filter_map(|dir| self.nodes.step(current, dir))
+                .find(|position| *position != previous)?;
+
+            if next == start {
+                return Some(path);
+            }
+            if !self.connections(current).contains(&next) {
+                return None;
             }
+            path.push(next);
         }
     }
 
     fn build(input: &str) -> Map {
-        let mut nodes = Vec::new();
-        let width: usize = input.lines().next().unwrap().len();
-        let height: usize = input.lines().count();
-        let mut start_position: (usize, usize) = (0, 0);
-
-        input.lines().for_each(|line| {
-            line.chars().for_each(|char| {
-                let node = match char {
-                    '-' => NodeState::Pipe(Directions::from(false, true, false, true)),
-                    '|' => NodeState::Pipe(Directions::from(true, false, true, false)),
-                    'F' => NodeState::Pipe(Directions::from(false, true, true, false)),
-                    '7' => NodeState::Pipe(Directions::from(false, false, true, true)),
-                    'L' => NodeState::Pipe(Directions::from(true, true, false, false)),
-                    'J' => NodeState::Pipe(Directions::from(true, false, false, true)),
-                    'S' => {
-                        start_position = (nodes.len() % width, nodes.len() / width);
-                        NodeState::Animal
-                    },
-                    _ => NodeState::Ground
-                };
-                nodes.push(node);
-            });
-        });
-        Map { 
-            size: (width, height),
-            start_position,
-            nodes
-        }
-    }
+        let nodes = Grid::parse(input, |char| match char {
+            '-' => NodeState::Pipe(Directions::from(false, true, false, true)),
+            '|' => NodeState::Pipe(Directions::from(true, false, true, false)),
+            'F' => NodeState::Pipe(Directions::from(false, true, true, false)),
+            '7' => NodeState::Pipe(Directions::from(false, false, true, true)),
+            'L' => NodeState::Pipe(Directions::from(true, true, false, false)),
+            'J' => NodeState::Pipe(Directions::from(true, false, false, true)),
+            'S' => NodeState::Animal,
+            _ => NodeState::Ground,
+        })
+        .unwrap();
+        let start_position = nodes.find(|node| matches!(node, NodeState::Animal));
 
-    fn empty(width: usize, height: usize) -> Map {
-        let mut nodes = Vec::new();
-        for i in 0..width*height {
-            nodes.push(NodeState::Ground);
-        }
         Map {
-            size: (width, height),
-            start_position: (0, 0),
-            nodes
-        }
-    }
-
-    fn print(&self) {
-        for y in 0..self.height() {
-            for x in 0..self.width() {
-                print!("{}", self.get_node_symbol((x, y)))
-            }
-            println!("");
+            start_position,
+            nodes,
         }
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    // Construct the map
-    println!("Building Map");
-    let map = Map::build(input);
-    println!("Map Built");
-    println!("Traversing Paths");
-
-    let mut agent_position = map.start_position.clone();
-    let mut agent_entry_direction: Direction;
-    
-    let mut final_agent_steps = 0;
-
-    let valid_dirs: Vec<Direction> = vec![Direction::NORTH, Direction::EAST, Direction::SOUTH, Direction::WEST].into_iter().filter(|d| {
-        let next_coords = d.get_next_coords(map.start_position.0, map.start_position.1);
-        match map.get_node(next_coords.0, next_coords.1) {
-            None => false,
-            Some(node_state) => {
-                match node_state {
-                    NodeState::Pipe(pd) => {
-                        pd.has(d.get_opposite())
-                    },
-                    _ => false
-                }
-            }
-        }
-    }).collect();
-    for (i, dir) in valid_dirs.iter().enumerate() {
-        // For each possible direction, run the agent until it fails. If the agent hits home again, we've found the cycle
-        agent_position = map.start_position.clone();
-        agent_position = dir.get_next_coords(agent_position.0, agent_position.1);
-        agent_entry_direction = dir.get_opposite();
-        final_agent_steps = 1;
-        let mut made_it_home: bool = false;
-        loop {
-            // Check to see if the next position is valid. if it isn't, this path is a fail. If it's the animal again, we're done and have our cycle
-            match map.get_node(agent_position.0, agent_position.1) {
-                None => {
-                    // We failed. Break the loop.
-                    break;
-                },
-                Some(node_state) => {
-                    match node_state {
-                        NodeState::Pipe(pipe_dirs) => {
-                            let out_dir = pipe_dirs.get_dir_vector().into_iter().filter(|check_dir| *check_dir != agent_entry_direction).last().unwrap();
-                            let next_coords = out_dir.get_next_coords(agent_position.0, agent_position.1);
-                            let next_node_state = map.get_node(next_coords.0, next_coords.1);
-                            match next_node_state {
-                                None => {
-                                    //Out of map, failed.
-                                    break;
-                                },
-                                Some(ns) => {
-                                    match ns {
-                                        NodeState::Animal => {
-                                            made_it_home = true;
-                                            break;
-                                        },
-                                        NodeState::Ground => {
-                                            break;
-                                        },
-                                        NodeState::Pipe(next_pipe_dir) => {
-                                            if next_pipe_dir.has(out_dir.get_opposite()) {
-                                                agent_position = next_coords;
-                                                agent_entry_direction = out_dir.get_opposite();
-                                                final_agent_steps += 1;
-                                                continue;
-                                            } else {
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        },
-                        _ => {
-                            panic!("Something went wrong. Agent offroading.");
-                        }
-                    }
-                }
-            };
-        }
-        if made_it_home {
-            break;
-        }
-    }
-    
-    Some((final_agent_steps + 1) / 2)
+pub fn parse(input: &str) -> Map {
+    Map::build(input)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    // Construct the map
-    println!("Building Map");
-    let map = Map::build(input);
-    println!("Map Built");
-
-    println!("Traversing Paths");
-    let mut agent_position = map.start_position.clone();
-    let mut agent_entry_direction: Direction;
-
-    let mut main_loop_points: Vec<(usize, usize)> = Vec::new();
-
-    let valid_dirs: Vec<Direction> = vec![Direction::NORTH, Direction::EAST, Direction::SOUTH, Direction::WEST].into_iter().filter(|d| {
-        let next_coords = d.get_next_coords(map.start_position.0, map.start_position.1);
-        match map.get_node(next_coords.0, next_coords.1) {
-            None => false,
-            Some(node_state) => {
-                match node_state {
-                    NodeState::Pipe(pd) => {
-                        pd.has(d.get_opposite())
-                    },
-                    _ => false
-                }
-            }
-        }
-    }).collect();
-    for (i, dir) in valid_dirs.iter().enumerate() {
-        // For each possible direction, run the agent until it fails. If the agent hits home again, we've found the cycle
-        agent_position = map.start_position.clone();
-        agent_position = dir.get_next_coords(agent_position.0, agent_position.1);
-        agent_entry_direction = dir.get_opposite();
-        let mut visited_points: Vec<(usize, usize)> = Vec::new();
-        visited_points.push(map.start_position.clone());
-        let mut made_it_home: bool = false;
-        loop {
-            visited_points.push(agent_position);
-            // Check to see if the next position is valid. if it isn't, this path is a fail. If it's the animal again, we're done and have our cycle
-            match map.get_node(agent_position.0, agent_position.1) {
-                None => {
-                    // We failed. Break the loop.
-                    break;
-                },
-                Some(node_state) => {
-                    match node_state {
-                        NodeState::Pipe(pipe_dirs) => {
-                            let out_dir = pipe_dirs.get_dir_vector().into_iter().filter(|check_dir| *check_dir != agent_entry_direction).last().unwrap();
-                            let next_coords = out_dir.get_next_coords(agent_position.0, agent_position.1);
-                            let next_node_state = map.get_node(next_coords.0, next_coords.1);
-                            match next_node_state {
-                                None => {
-                                    //Out of map, failed.
-                                    break;
-                                },
-                                Some(ns) => {
-                                    match ns {
-                                        NodeState::Animal => {
-                                            made_it_home = true;
-                                            break;
-                                        },
-                                        NodeState::Ground => {
-                                            break;
-                                        },
-                                        NodeState::Pipe(next_pipe_dir) => {
-                                            if next_pipe_dir.has(out_dir.get_opposite()) {
-                                                agent_position = next_coords;
-                                                agent_entry_direction = out_dir.get_opposite();
-                                                continue;
-                                            } else {
-                                                break;
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        },
-                        _ => {
-                            panic!("Something went wrong. Agent offroading.");
-                        }
-                    }
-                }
-            };
-        }
-        if made_it_home {
-            main_loop_points = visited_points;
-            break;
-        }
-    };
-
-    println!("Building Clean Map");
-    let mut clean_map = Map::empty(map.width(), map.height());
-    for point in main_loop_points {
-        let index = point.0 + point.1 * clean_map.width();
-        clean_map.nodes[index] = map.nodes[index].clone();
-    }
-    clean_map.start_position = map.start_position.clone();
-
-    // Fix the start potision to be a pipe
-    {
-        let (x, y) = clean_map.start_position;
-        let north = clean_map.is_pipe_pointing(x, y.wrapping_sub(1), Direction::SOUTH);
-        let east = clean_map.is_pipe_pointing(x+1, y, Direction::WEST);
-        let south = clean_map.is_pipe_pointing(x, y+1, Direction::NORTH);
-        let west = clean_map.is_pipe_pointing(x.wrapping_sub(1), y, Direction::EAST);
-        let index = x + (clean_map.width() * y);
-        clean_map.nodes[index] = NodeState::Pipe(Directions {
-            north, south, east, west
-        })
-    }
+pub fn part_one(map: &Map) -> Option<u32> {
+    // the loop is walked in both directions from the animal, they meet halfway.
+    let main_loop = map.main_loop()?;
+    Some((main_loop.len() / 2).try_into().unwrap())
+}
 
-    clean_map.print();
-
-    let mut inside_points: usize = 0;
-    for index in 0..clean_map.width()*clean_map.height() {
-        // iterate over each point and check if it's inside
-        let mut intersections = 0;
-        let mut x = index % clean_map.width();
-        let mut y = index / clean_map.width();
-        if clean_map.get_node_symbol((x, y)) != "." {continue}
-        while x > 0 && y > 0 {
-            x -= 1;
-            y -= 1;
-            let symbol = clean_map.get_node_symbol((x, y));
-            match symbol {
-                "-" | "|" | "F" | "J" => {intersections += 1}
-                _ => {}
-            };
-        }
-        if intersections % 2 == 1 {
-            inside_points += 1;
-        }
-    }
+pub fn part_two(map: &Map) -> Option<u32> {
+    let vertices: Vec<Point2<i64>> = map
+        .main_loop()?
+        .into_iter()
+        .map(|(x, y)| Point2::new(x as i64, y as i64))
+        .collect();
 
-    Some(inside_points.try_into().unwrap())
+    Some(polygon::interior_points(&vertices).try_into().unwrap())
 }
 
 #[cfg(test)]
@@ -420,15 +176,26 @@ mod tests {
 L-J";
         let map = Map::build(input);
         let values = vec![2, 1, 1, 1, 0, 1, 1, 1, 1];
-        for i in 0..9 {
-            assert!(match map.nodes[i] {
-                NodeState::Ground => values[i] == 0,
-                NodeState::Pipe(_) => values[i] == 1,
-                NodeState::Animal => values[i] == 2
-            }, "Map Value Mismatch");
+        for ((_, node), value) in map.nodes.iter().zip(values) {
+            assert!(
+                match node {
+                    NodeState::Ground => value == 0,
+                    NodeState::Pipe(_) => value == 1,
+                    NodeState::Animal => value == 2,
+                },
+                "Map Value Mismatch"
+            );
         }
-        assert_eq!(map.size, (3, 3), "Map Size Mismatch");
-        assert_eq!(map.start_position, (0, 0), "Map Start Position incorrect");
+        assert_eq!(
+            (map.nodes.width(), map.nodes.height()),
+            (3, 3),
+            "Map Size Mismatch"
+        );
+        assert_eq!(
+            map.start_position,
+            Some((0, 0)),
+            "Map Start Position incorrect"
+        );
     }
 
     #[test]
@@ -440,26 +207,85 @@ L-J";
         let values = vec![2, 1, 1, 1, 0, 1, 1, 1, 1];
         for x in 0..3 {
             for y in 0..3 {
-                assert!(match map.get_node(x, y) {
-                    Some(NodeState::Ground) => values[x + y * map.width()] == 0,
-                    Some(NodeState::Pipe(_)) => values[x + y * map.width()] == 1,
-                    Some(NodeState::Animal) => values[x + y * map.width()] == 2,
-                    None => false
-                }, "Map Value Mismatch");
-            } 
+                assert!(
+                    match map.nodes.get((x, y)) {
+                        Some(NodeState::Ground) => values[x + y * map.nodes.width()] == 0,
+                        Some(NodeState::Pipe(_)) => values[x + y * map.nodes.width()] == 1,
+                        Some(NodeState::Animal) => values[x + y * map.nodes.width()] == 2,
+                        None => false,
+                    },
+                    "Map Value Mismatch"
+                );
+            }
         }
+    }
+
+    #[test]
+    fn test_main_loop() {
+        let input = "..F7.
+.FJ|.
+SJ.L7
+|F--J
+LJ...";
+        let map = Map::build(input);
+        let main_loop = map.main_loop().unwrap();
+        assert_eq!(main_loop.len(), 16);
+        assert_eq!(main_loop[0], (0, 2));
+        assert!(main_loop
+            .windows(2)
+            .all(|w| map.nodes.neighbours4(w[0]).any(|n| n == w[1])));
+    }
 
+    #[test]
+    fn test_main_loop_skips_dead_ends() {
+        let input = ".|...
+.S-7.
+.|.|.
+.L-J.
+.....";
+        let map = Map::build(input);
+        assert_eq!(map.main_loop().map(|main_loop| main_loop.len()), Some(8));
+        assert_eq!(part_two(&map), Some(1));
+    }
+
+    #[test]
+    fn test_main_loop_without_loop() {
+        assert_eq!(Map::build("S-7\n...").main_loop(), None);
+        assert_eq!(Map::build("S..\n...").main_loop(), None);
+    }
+
+    #[test]
+    fn test_map_without_animal() {
+        let map = Map::build("F-7\n|.|\nL-J");
+        assert_eq!(map.start_position, None);
+        assert_eq!(part_one(&map), None);
+        assert_eq!(part_two(&map), None);
+    }
+
+    #[test]
+    fn test_part_one_ignores_dead_ends() {
+        let input = ".|...
+.|...
+.|...
+.|...
+.|...
+.S-7.
+.|.|.
+.L-J.";
+        assert_eq!(part_one(&Map::build(input)), Some(4));
     }
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let map = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&map);
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let map = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&map);
         assert_eq!(result, Some(1));
     }
 }
@@ -0,0 +1,162 @@
+//! Skipping ahead in simulations that eventually repeat themselves.
+//!
+//! ```
+//! use advent_of_code::cycle;
+//!
+//! // 1, 3, 9, 27 % 10 = 7, 21 % 10 = 1, ...
+//! let state = cycle::state_at(1, |n| n * 3 % 10, 1_000_000_000);
+//! assert_eq!(state, 1);
+//! ```
+
+use std::{collections::HashMap, hash::Hash};
+
+/// The repeating part of a sequence of states `s(0), s(1) = step(s(0)), ...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// The first step that is part of the cycle.
+    pub start: usize,
+    /// The number of steps after which the cycle repeats.
+    pub length: usize,
+    /// Every state up to the end of the first pass of the cycle.
+    states: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Cycle<S> {
+    /// Simulates until a state repeats.
+    /// This never returns if the sequence does not repeat, e.g. because the state grows forever.
+    pub fn find(initial: S, step: impl FnMut(&S) -> S) -> Self {
+        match simulate(initial, step, None) {
+            Simulation::Cycle(cycle) => cycle,
+            Simulation::Reached(_) => unreachable!("simulation without limit stopped early"),
+        }
+    }
+
+    /// The state after `n` steps.
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.index_of(n)]
+    }
+
+    /// The earliest step with the same state as step `n`.
+    pub fn index_of(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// The state after `n` steps. Simulates until the state repeats or step `n` is reached, whichever comes first.
+pub fn state_at<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    match simulate(initial, step, Some(n)) {
+        Simulation::Cycle(cycle) => cycle.state_at(n).clone(),
+        Simulation::Reached(state) => state,
+    }
+}
+
+enum Simulation<S> {
+    Cycle(Cycle<S>),
+    Reached(S),
+}
+
+fn simulate<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: Option<usize>,
+) -> Simulation<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+
+    loop {
+        let i = states.len();
+
+        if let Some(&start) = seen.get(&state) {
+            return Simulation::Cycle(Cycle {
+                start,
+                length: i - start,
+                states,
+            });
+        }
+
+        if limit == Some(i) {
+            return Simulation::Reached(state);
+        }
+
+        let next = step(&state);
+        seen.insert(state.clone(), i);
+        states.push(state);
+        state = next;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{state_at, Cycle};
+
+    fn brute_force<S>(initial: S, step: impl Fn(&S) -> S, n: usize) -> S {
+        (0..n).fold(initial, |state, _| step(&state))
+    }
+
+    #[test]
+    fn finds_pure_cycles() {
+        let cycle = Cycle::find(0, |n| (n + 1) % 5);
+        assert_eq!((cycle.start, cycle.length), (0, 5));
+        assert_eq!(*cycle.state_at(1_000_000_002), 2);
+    }
+
+    #[test]
+    fn finds_cycles_with_a_tail() {
+        // 0, 1, 2, 3, 4, 5, 6, 3, 4, ...
+        let step = |n: &u32| if *n == 6 { 3 } else { n + 1 };
+        let cycle = Cycle::find(0, step);
+        assert_eq!((cycle.start, cycle.length), (3, 4));
+        assert_eq!(cycle.index_of(2), 2);
+        assert_eq!(cycle.index_of(7), 3);
+
+        for n in 0..100 {
+            assert_eq!(*cycle.state_at(n), brute_force(0, step, n));
+        }
+    }
+
+    #[test]
+    fn finds_fixed_points() {
+        let cycle = Cycle::find(10, |n: &u32| n.saturating_sub(3));
+        assert_eq!((cycle.start, cycle.length), (4, 1));
+        assert_eq!(*cycle.state_at(1_000_000_000), 0);
+    }
+
+    #[test]
+    fn matches_brute_force_for_state_at() {
+        let step = |n: &u64| (n * n + 1) % 255;
+        for n in [0, 1, 5, 50, 500, 5000] {
+            assert_eq!(state_at(2, step, n), brute_force(2, step, n));
+        }
+    }
+
+    #[test]
+    fn supports_compound_states() {
+        // a north-facing walker turning right on every step returns to its start after 4 steps.
+        let step = |&(x, y, dx, dy): &(i32, i32, i32, i32)| (x + dx, y + dy, -dy, dx);
+        let cycle = Cycle::find((0, 0, 0, -1), step);
+        assert_eq!((cycle.start, cycle.length), (0, 4));
+        assert_eq!(*cycle.state_at(1_000_000_001), (0, -1, 1, 0));
+    }
+
+    #[test]
+    fn stops_at_step_before_cycle() {
+        let mut steps = 0;
+        let state = state_at(
+            0u64,
+            |n| {
+                steps += 1;
+                n + 1
+            },
+            10,
+        );
+        assert_eq!(state, 10);
+        assert_eq!(steps, 10);
+    }
+}
@@ -0,0 +1,300 @@
+//! Points, directions and distances on a 2D lattice.
+//!
+//! Like [`Grid`](crate::grid::Grid), the y axis points downwards: [`Dir4::North`] decreases `y`.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or vector in 2D space.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + PartialOrd + Sub<Output = T> + Add<Output = T>> Point2<T> {
+    /// Sum of the distances along each axis, the number of orthogonal steps between two points.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Largest distance along any axis, the number of orthogonal or diagonal steps between two points.
+    pub fn chebyshev(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+}
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl Point2<isize> {
+    /// The neighbouring point in a direction.
+    pub fn step(self, dir: impl Step) -> Self {
+        self + dir.offset()
+    }
+}
+
+impl Point2<usize> {
+    /// The neighbouring point in a direction, [`None`] if it would leave the positive quadrant.
+    pub fn checked_step(self, dir: impl Step) -> Option<Self> {
+        let offset = dir.offset();
+        Some(Self::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+
+    /// The neighbouring point in a direction, [`None`] if it would leave a grid of the given size.
+    pub fn step_within(self, dir: impl Step, width: usize, height: usize) -> Option<Self> {
+        self.checked_step(dir)
+            .filter(|next| next.x < width && next.y < height)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A direction that moves a point by a fixed offset.
+pub trait Step: Copy {
+    fn offset(self) -> Point2<isize>;
+}
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Dir4; 4] = [Dir4::North, Dir4::East, Dir4::South, Dir4::West];
+
+    pub fn opposite(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    /// Turns by 90 degrees counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    /// Turns by 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+}
+
+impl Step for Dir4 {
+    fn offset(self) -> Point2<isize> {
+        Dir8::from(self).offset()
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::North,
+        Dir8::NorthEast,
+        Dir8::East,
+        Dir8::SouthEast,
+        Dir8::South,
+        Dir8::SouthWest,
+        Dir8::West,
+        Dir8::NorthWest,
+    ];
+
+    pub fn opposite(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// Turns by 45 degrees counter-clockwise.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    /// Turns by 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+}
+
+impl Step for Dir8 {
+    fn offset(self) -> Point2<isize> {
+        let (x, y) = match self {
+            Dir8::North => (0, -1),
+            Dir8::NorthEast => (1, -1),
+            Dir8::East => (1, 0),
+            Dir8::SouthEast => (1, 1),
+            Dir8::South => (0, 1),
+            Dir8::SouthWest => (-1, 1),
+            Dir8::West => (-1, 0),
+            Dir8::NorthWest => (-1, -1),
+        };
+        Point2::new(x, y)
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::North => Dir8::North,
+            Dir4::East => Dir8::East,
+            Dir4::South => Dir8::South,
+            Dir4::West => Dir8::West,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{Dir4, Dir8, Point2, Step};
+
+    #[test]
+    fn does_arithmetic() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a + b, Point2::new(-2, 7));
+        assert_eq!(a - b, Point2::new(4, -3));
+        assert_eq!(b * 2, Point2::new(-6, 10));
+        assert_eq!(-a, Point2::new(-1, -2));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+        assert_eq!(<(i32, i32)>::from(c), (-3, 5));
+    }
+
+    #[test]
+    fn measures_distances() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(-3, 5);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+
+        let a = Point2::<usize>::new(4, 0);
+        let b = Point2::<usize>::new(1, 6);
+        assert_eq!(a.manhattan(b), 9);
+        assert_eq!(b.chebyshev(a), 6);
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Dir4::North.turn_left(), Dir4::West);
+        assert_eq!(Dir4::West.turn_right(), Dir4::North);
+        assert_eq!(Dir4::East.opposite(), Dir4::West);
+        assert_eq!(Dir8::North.turn_left(), Dir8::NorthWest);
+        assert_eq!(Dir8::NorthWest.turn_right(), Dir8::North);
+        assert_eq!(Dir8::SouthEast.opposite(), Dir8::NorthWest);
+
+        for dir in Dir8::ALL {
+            assert_eq!(dir.offset(), -dir.opposite().offset());
+        }
+    }
+
+    #[test]
+    fn steps_points() {
+        assert_eq!(Point2::new(0, 0).step(Dir4::North), Point2::new(0, -1));
+        assert_eq!(Point2::new(0, 0).step(Dir8::SouthEast), Point2::new(1, 1));
+
+        let corner = Point2::<usize>::new(0, 0);
+        assert_eq!(corner.checked_step(Dir4::North), None);
+        assert_eq!(corner.checked_step(Dir8::SouthWest), None);
+        assert_eq!(corner.checked_step(Dir4::East), Some(Point2::new(1, 0)));
+
+        let edge = Point2::<usize>::new(2, 1);
+        assert_eq!(edge.step_within(Dir4::East, 3, 3), None);
+        assert_eq!(edge.step_within(Dir4::South, 3, 3), Some(Point2::new(2, 2)));
+    }
+}
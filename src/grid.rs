@@ -0,0 +1,437 @@
+//! A rectangular grid of cells, as used by most map-based puzzles.
+//!
+//! Positions are `(x, y)` tuples with the origin in the top-left corner, `x` growing to the right and `y` downwards.
+
+use std::{
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::geom::{Dir4, Dir8, Point2, Step};
+
+/// A position in a [`Grid`].
+pub type Pos = (usize, usize);
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells in row-major order. A grid without rows is empty, with a width of 0.
+    ///
+    /// # Panics
+    /// Panics if the number of cells does not match `width * height`, or if the grid has rows but no columns.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width > 0 || height == 0,
+            "a grid with {height} rows needs at least one column"
+        );
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells, got {}",
+            cells.len()
+        );
+        Self {
+            cells,
+            width: if height == 0 { 0 } else { width },
+            height,
+        }
+    }
+
+    /// Creates a grid with every cell set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a grid from lines of text, mapping every character to a cell.
+    /// Trailing line breaks and empty lines are ignored, rows of different length are an error.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+
+        for (i, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let len = line.chars().count();
+
+            match width {
+                None => width = Some(len),
+                Some(width) if width != len => {
+                    return Err(GridError::Ragged {
+                        line: i + 1,
+                        expected: width,
+                        found: len,
+                    })
+                }
+                _ => {}
+            }
+
+            cells.extend(line.chars().map(&mut cell));
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self::new(width, height, cells)),
+            _ => Err(GridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a position lies inside of the grid.
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns the cell at a position, [`None`] if it lies outside of the grid.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    /// Returns the cell at a position mutably, [`None`] if it lies outside of the grid.
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Index of a position in the row-major cells.
+    pub fn index_of(&self, (x, y): Pos) -> Option<usize> {
+        self.contains((x, y)).then(|| x + y * self.width)
+    }
+
+    /// Position of an index into the row-major cells.
+    pub fn position_of(&self, index: usize) -> Pos {
+        (index % self.width, index / self.width)
+    }
+
+    /// The neighbouring position in a direction, [`None`] if it lies outside of the grid.
+    pub fn step(&self, pos: Pos, dir: impl Step) -> Option<Pos> {
+        Point2::from(pos)
+            .step_within(dir, self.width, self.height)
+            .map(Pos::from)
+    }
+
+    /// The orthogonal neighbours of a position that lie inside of the grid, clockwise starting north.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// The orthogonal and diagonal neighbours of a position that lie inside of the grid, clockwise starting north.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| self.step(pos, dir))
+    }
+
+    /// All positions of the grid in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        (0..self.cells.len()).map(|i| self.position_of(i))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Positions of all cells matching a predicate, in row-major order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter_map(move |(pos, cell)| predicate(cell).then_some(pos))
+    }
+
+    /// Position of the first cell matching a predicate, in row-major order.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.find_all(predicate).next()
+    }
+
+    /// A row of the grid.
+    ///
+    /// # Panics
+    /// Panics if `y` lies outside of the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// All rows of the grid, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // the width of an empty grid is 0, which is not a valid chunk size.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of a column, top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `x` lies outside of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is outside of the grid");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// All columns of the grid, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = self.columns().flat_map(|c| c.cloned()).collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .flat_map(|x| (0..self.height).rev().map(move |y| (x, y)))
+            .map(|pos| self[pos].clone())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Rotates the grid by 90 degrees counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self
+    where
+        T: Clone,
+    {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| (0..self.height).map(move |y| (x, y)))
+            .map(|pos| self[pos].clone())
+            .collect();
+        Self::new(self.height, self.width, cells)
+    }
+
+    /// Applies a function to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} is outside of the grid"))
+    }
+}
+
+/// Displays the grid in the format of the puzzle input, one line per row.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Grid::parse(s, |c| c)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// The input does not contain any cells.
+    Empty,
+    /// A row has a different length than the first row.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => write!(f, "grid is empty"),
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected a row of {expected} cells, found {found}"
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{Grid, GridError};
+    use crate::geom::Dir4;
+
+    fn get_mock_grid() -> Grid<char> {
+        "abc\ndef\n".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = get_mock_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(0, 0)], 'a');
+        assert_eq!(grid[(2, 1)], 'f');
+
+        let digits = Grid::parse("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.row(1), &[3, 4]);
+
+        let trailing = "ab\ncd\n\n".parse::<Grid<char>>().unwrap();
+        assert_eq!((trailing.width(), trailing.height()), (2, 2));
+        assert_eq!("ab\r\ncd\r\n\r\n".parse::<Grid<char>>(), Ok(trailing));
+        assert_eq!("\n\n".parse::<Grid<char>>(), Err(GridError::Empty));
+    }
+
+    #[test]
+    fn rejects_malformed_grids() {
+        assert_eq!(
+            "abc\nde\n".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+    }
+
+    #[test]
+    #[should_panic(expected = "needs at least one column")]
+    fn panics_for_rows_without_columns() {
+        Grid::<char>::new(0, 2, vec![]);
+    }
+
+    #[test]
+    fn handles_empty_grids() {
+        let grid = Grid::filled(3, 0, '.');
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.transpose(), grid);
+        assert_eq!(Grid::<char>::new(0, 0, vec![]).to_string(), "");
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let mut grid = get_mock_grid();
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 2)), None);
+        assert_eq!(grid.get((usize::MAX, 0)), None);
+        assert_eq!(grid.get((0, usize::MAX)), None);
+
+        *grid.get_mut((1, 1)).unwrap() = 'x';
+        assert_eq!(grid.to_string(), "abc\ndxf");
+        assert!(grid.get_mut((1, 2)).is_none());
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = Grid::filled(3, 3, '.');
+        assert_eq!(
+            grid.neighbours4((1, 1)).collect::<Vec<_>>(),
+            [(1, 0), (2, 1), (1, 2), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<_>>(),
+            [(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.step((1, 2), Dir4::North), Some((1, 1)));
+        assert_eq!(grid.step((1, 2), Dir4::South), None);
+        assert_eq!(
+            grid.neighbours8((2, 2)).collect::<Vec<_>>(),
+            [(2, 1), (1, 2), (1, 1)]
+        );
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = get_mock_grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            [['a', 'b', 'c'], ['d', 'e', 'f']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            ["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn transposes_and_rotates() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.rotate_cw().rotate_cw().to_string(), "fed\ncba");
+    }
+
+    #[test]
+    fn finds_positions() {
+        let grid: Grid<char> = "#.#\n..#".parse().unwrap();
+        assert_eq!(
+            grid.find_all(|c| *c == '#').collect::<Vec<_>>(),
+            [(0, 0), (2, 0), (2, 1)]
+        );
+        assert_eq!(grid.find(|c| *c == '.'), Some((1, 0)));
+        assert_eq!(grid.find(|c| *c == 'S'), None);
+    }
+
+    #[test]
+    fn displays_in_input_format() {
+        let input = "#.#\n..#";
+        let grid: Grid<char> = input.parse().unwrap();
+        assert_eq!(grid.to_string(), input);
+        assert_eq!(grid.map(|c| u8::from(*c == '#')).to_string(), "101\n001");
+    }
+}
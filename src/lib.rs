@@ -1,3 +1,12 @@
+pub mod cycle;
+pub mod geom;
+pub mod grid;
+pub mod math;
+pub mod parse;
+pub mod polygon;
+pub mod ranges;
+pub mod search;
+pub mod sequence;
 pub mod template;
 
 // Use this file to add helper functions and additional modules.
@@ -1,40 +1,45 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{download, examples, read, scaffold, solve, watch};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, PuzzleId};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        args::{parse_iterations, parse_puzzle},
+        runner::{Format, RunOptions},
+        PuzzleId,
+    };
+    use std::{path::PathBuf, process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
+            template: Option<String>,
+            force: bool,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            options: RunOptions,
         },
-        All {
+        Watch {
+            puzzle: PuzzleId,
             release: bool,
         },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
-        },
         #[cfg(feature = "today")]
         Today,
     }
@@ -43,35 +48,66 @@ mod args {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
-
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
+                puzzle: parse_puzzle(&mut args)?,
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("examples") => AppArguments::Examples {
+                puzzle: parse_puzzle(&mut args)?,
             },
+            Some("scaffold") => {
+                let download = args.contains("--download");
+                let template = args.opt_value_from_str("--template")?;
+                let force = args.contains("--force");
+
+                AppArguments::Scaffold {
+                    puzzle: parse_puzzle(&mut args)?,
+                    download,
+                    template,
+                    force,
+                }
+            }
+            Some("solve") => {
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let time = args.contains("--time");
+                let budget: Option<u64> = args.opt_value_from_str("--budget")?;
+                let iterations = parse_iterations(&mut args)?;
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+                let records = args.opt_value_from_str::<_, PathBuf>("--records")?;
+
+                if format == Format::Json && records.is_none() {
+                    return Err(
+                        "`--format json` needs `--records <path>` to write the records to.".into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    puzzle: parse_puzzle(&mut args)?,
+                    release,
+                    dhat,
+                    options: RunOptions {
+                        time,
+                        submit,
+                        format,
+                        records,
+                        budget: budget.map(Duration::from_millis),
+                        iterations,
+                        ..RunOptions::default()
+                    },
+                }
+            }
+            Some("watch") => {
+                let release = args.contains("--release");
+
+                AppArguments::Watch {
+                    puzzle: parse_puzzle(&mut args)?,
+                    release,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -100,29 +136,35 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::Watch { puzzle, release } => watch::handle(puzzle, release),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle } => examples::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
+                download,
+                template,
+                force,
+            } => {
+                scaffold::handle(puzzle, template.as_deref(), force);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                options,
+            } => solve::handle(puzzle, release, dhat, &options),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = PuzzleId::from(day);
+                        scaffold::handle(puzzle, None, false);
+                        download::handle(puzzle);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
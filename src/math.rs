@@ -0,0 +1,298 @@
+//! Number theory on `i128`.
+//!
+//! Modular arithmetic never overflows for moduli up to `i128::MAX`, products are reduced as they are computed.
+//! Results that do not fit into an `i128` themselves, e.g. the lcm of very large numbers, panic.
+
+/// The greatest common divisor, always non-negative. `gcd(0, 0)` is `0`.
+pub fn gcd(a: i128, b: i128) -> i128 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    i128::try_from(a).expect("gcd does not fit into an i128")
+}
+
+/// The least common multiple, always non-negative. `lcm(0, n)` is `0`.
+pub fn lcm(a: i128, b: i128) -> i128 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    (a / gcd(a, b))
+        .checked_mul(b)
+        .expect("lcm does not fit into an i128")
+        .abs()
+}
+
+/// The greatest common divisor of all numbers, `0` for none.
+pub fn gcd_all<T: Into<i128>>(numbers: impl IntoIterator<Item = T>) -> i128 {
+    numbers.into_iter().fold(0, |acc, n| gcd(acc, n.into()))
+}
+
+/// The least common multiple of all numbers, `1` for none. Useful for aligning several cycles.
+pub fn lcm_all<T: Into<i128>>(numbers: impl IntoIterator<Item = T>) -> i128 {
+    numbers.into_iter().fold(1, |acc, n| lcm(acc, n.into()))
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g = gcd(a, b)`.
+///
+/// # Panics
+/// Panics exactly if `gcd(a, b) == 2^127`, which does not fit into an `i128`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a.unsigned_abs(), b.unsigned_abs());
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let rem = old_r % r;
+        if rem == 0 {
+            // the coefficients of the zero remainder are never needed and may not fit.
+            (old_r, old_x, old_y) = (r, x, y);
+            break;
+        }
+        // a non-zero remainder means r >= 2, so q <= 2^126 and all coefficients stay below 2^126.
+        let q = (old_r / r) as i128;
+        (old_r, r) = (r, rem);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+
+    let g = i128::try_from(old_r).expect("gcd does not fit into an i128");
+    let x = if a < 0 { -old_x } else { old_x };
+    let y = if b < 0 { -old_y } else { old_y };
+    (g, x, y)
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, [`None`] if `a` and `m` are not coprime.
+pub fn mod_inverse(a: i128, m: i128) -> Option<i128> {
+    assert!(m > 0, "modulus must be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// `a * b mod m` in `0..m`, without overflowing.
+pub fn mul_mod(a: i128, b: i128, m: i128) -> i128 {
+    assert!(m > 0, "modulus must be positive");
+    let (a, b) = (a.rem_euclid(m), b.rem_euclid(m));
+
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    // double and add, both operands are below 2^127 so their sum fits into an u128.
+    let (mut a, mut b, m) = (a as u128, b as u128, m as u128);
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = (result + a) % m;
+        }
+        a = (a + a) % m;
+        b >>= 1;
+    }
+    result as i128
+}
+
+/// `base^exp mod m` in `0..m`, by repeated squaring.
+pub fn mod_pow(base: i128, exp: u128, m: i128) -> i128 {
+    assert!(m > 0, "modulus must be positive");
+    let mut base = base.rem_euclid(m);
+    let mut exp = exp;
+    let mut result = 1 % m;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese remainder theorem.
+/// Moduli do not need to be coprime. Returns the solution as `(x, lcm of the moduli)` with `x` in
+/// `0..lcm`, or [`None`] if the congruences contradict each other.
+pub fn crt(congruences: impl IntoIterator<Item = (i128, i128)>) -> Option<(i128, i128)> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, m), (residue, modulus)| {
+            assert!(modulus > 0, "modulus must be positive");
+            let residue = residue.rem_euclid(modulus);
+            let g = gcd(m, modulus);
+
+            let diff = residue - x;
+            if diff % g != 0 {
+                return None;
+            }
+
+            // x + m * k ≡ residue (mod modulus)  <=>  (m / g) * k ≡ diff / g (mod modulus / g)
+            let step = modulus / g;
+            let k = mul_mod(diff / g, mod_inverse(m / g, step)?, step);
+            let lcm = lcm(m, modulus);
+            // both summands are below lcm, so their sum fits into an u128.
+            let x = (x as u128 + mul_mod(m, k, lcm) as u128) % lcm as u128;
+            Some((x as i128, lcm))
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{crt, extended_gcd, gcd, gcd_all, lcm, lcm_all, mod_inverse, mod_pow, mul_mod};
+
+    // 2^127 - 1 is a mersenne prime.
+    const BIG_PRIME: i128 = i128::MAX;
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(-12, 18), 6);
+        assert_eq!(gcd(12, -18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(gcd(i128::MAX, i128::MAX), i128::MAX);
+
+        assert_eq!(lcm(4, 6), 12);
+        assert_eq!(lcm(-4, 6), 12);
+        assert_eq!(lcm(0, 6), 0);
+        assert_eq!(lcm(1 << 100, 1 << 120), 1 << 120);
+    }
+
+    #[test]
+    #[should_panic(expected = "lcm does not fit")]
+    fn panics_if_lcm_overflows() {
+        lcm(BIG_PRIME, 2);
+    }
+
+    #[test]
+    fn folds_iterators() {
+        assert_eq!(lcm_all([2u32, 3, 4, 5]), 60);
+        assert_eq!(
+            lcm_all([18_961u64, 12_169, 17_263, 13_301, 14_999, 16_697]),
+            7_309_459_565_207
+        );
+        assert_eq!(lcm_all(Vec::<i64>::new()), 1);
+        assert_eq!(gcd_all([12i32, -18, 30]), 6);
+        assert_eq!(gcd_all(Vec::<i64>::new()), 0);
+    }
+
+    #[test]
+    fn satisfies_bezout_identity() {
+        for a in -30..=30 {
+            for b in -30..=30 {
+                let (g, x, y) = extended_gcd(a, b);
+                assert_eq!(g, gcd(a, b), "gcd({a}, {b})");
+                assert_eq!(a * x + b * y, g, "bezout({a}, {b})");
+            }
+        }
+
+        let (a, b) = (BIG_PRIME, 1 << 126);
+        let (g, x, y) = extended_gcd(a, b);
+        assert_eq!(g, 1);
+        assert_eq!(mul_mod(a, x, b), 1);
+        assert_eq!(mul_mod(b, y, a), 1);
+    }
+
+    #[test]
+    fn handles_extended_gcd_of_min() {
+        assert_eq!(extended_gcd(i128::MIN, -1), (1, 0, -1));
+        assert_eq!(extended_gcd(-1, i128::MIN), (1, -1, 0));
+        assert_eq!(extended_gcd(i128::MIN, 1 << 100), (1 << 100, 0, 1));
+
+        let (g, x, y) = extended_gcd(i128::MIN, 3);
+        assert_eq!(g, 1);
+        // a * x + b * y = 1 holds exactly, only the intermediate products leave the i128 range.
+        assert_eq!(i128::MIN.wrapping_mul(x).wrapping_add(y.wrapping_mul(3)), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd does not fit")]
+    fn panics_if_extended_gcd_overflows() {
+        extended_gcd(i128::MIN, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd does not fit")]
+    fn panics_if_extended_gcd_overflows_for_swapped_arguments() {
+        extended_gcd(0, i128::MIN);
+    }
+
+    #[test]
+    fn inverts_modulo() {
+        for m in 1..50i128 {
+            for a in -50..50 {
+                let brute_force = (0..m).find(|x| (a * x).rem_euclid(m) == 1 % m);
+                assert_eq!(mod_inverse(a, m), brute_force, "inverse of {a} mod {m}");
+            }
+        }
+
+        let inverse = mod_inverse(3, BIG_PRIME).unwrap();
+        assert_eq!(mul_mod(3, inverse, BIG_PRIME), 1);
+    }
+
+    #[test]
+    fn multiplies_without_overflow() {
+        assert_eq!(mul_mod(-7, 3, 5), 4);
+        assert_eq!(mul_mod(BIG_PRIME - 1, BIG_PRIME - 1, BIG_PRIME), 1);
+        assert_eq!(mul_mod(1 << 100, 1 << 100, BIG_PRIME), 1 << 73);
+    }
+
+    #[test]
+    fn raises_to_powers() {
+        for m in 1..20 {
+            for base in -5..5 {
+                let mut expected = 1 % m;
+                for exp in 0..20 {
+                    assert_eq!(mod_pow(base, exp, m), expected, "{base}^{exp} mod {m}");
+                    expected = (expected * base).rem_euclid(m);
+                }
+            }
+        }
+
+        // fermat's little theorem
+        assert_eq!(mod_pow(3, BIG_PRIME as u128 - 1, BIG_PRIME), 1);
+        assert_eq!(mod_pow(2, 127, BIG_PRIME), 1);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt([(-1, 4), (3, 8)]), Some((3, 8)));
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn matches_brute_force_for_crt() {
+        for m1 in 1..12 {
+            for m2 in 1..12 {
+                for a1 in 0..m1 {
+                    for a2 in 0..m2 {
+                        let lcm = lcm(m1, m2);
+                        let brute_force = (0..lcm).find(|x| x % m1 == a1 && x % m2 == a2);
+                        assert_eq!(
+                            crt([(a1, m1), (a2, m2)]),
+                            brute_force.map(|x| (x, lcm)),
+                            "x ≡ {a1} (mod {m1}), x ≡ {a2} (mod {m2})"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solves_congruences_with_large_moduli() {
+        let moduli = [999_999_999_989, 1_000_000_000_039, 1_000_000_000_061];
+        let x = 123_456_789_012_345_678_901_234_567_890;
+        let (solution, lcm) = crt(moduli.map(|m| (x % m, m))).unwrap();
+        assert_eq!(lcm, moduli.iter().product());
+        assert_eq!(solution, x % lcm);
+
+        // shared factors of 2^60 in both moduli.
+        let (a, b) = ((1i128 << 60) * 3, (1i128 << 60) * 5);
+        let x = (1i128 << 61) + 7;
+        assert_eq!(crt([(x % a, a), (x % b, b)]), Some((x, (1i128 << 60) * 15)));
+    }
+}
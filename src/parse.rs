@@ -0,0 +1,261 @@
+//! Helpers for turning puzzle input into typed values.
+//!
+//! Errors point at the line and column of the input that could not be parsed. Helpers that work on a single
+//! line report line 1, [`lines`] and [`Block`] move them to the line of the input they were called for.
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// Extracts all integers from a line, e.g. `[1, -2, 3]` from `"x=1, y=-2..3"`.
+/// A `-` directly in front of a digit is read as a sign, unless it follows a digit: ranges like `2-4` are `[2, 4]`.
+pub fn ints<T>(line: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let bytes = line.as_bytes();
+    let mut ints = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let is_sign = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if !is_sign && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        let token = &line[start..i];
+        let value = token.parse().map_err(|err| {
+            ParseError::new(
+                1,
+                column_of(line, start),
+                format!("invalid integer `{token}`: {err}"),
+            )
+        })?;
+        ints.push(value);
+    }
+
+    Ok(ints)
+}
+
+/// Parses every line of the input with `f`.
+pub fn lines<T>(
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| f(line).map_err(|err| err.shifted(i)))
+        .collect()
+}
+
+/// Parses every line of the input into a `T` via [`FromStr`].
+pub fn records<T>(input: &str) -> Result<Vec<T>, ParseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    lines(input, |line| {
+        line.parse()
+            .map_err(|err| ParseError::new(1, 1, format!("invalid record `{line}`: {err}")))
+    })
+}
+
+/// Splits the input into blocks of lines separated by blank lines.
+pub fn blocks(input: &str) -> Vec<Block<'_>> {
+    let mut blocks = vec![];
+    // byte offset and line number of the block that is currently being read.
+    let mut current: Option<(usize, usize)> = None;
+    let mut offset = 0;
+
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((start, line)) = current.take() {
+                blocks.push(Block::new(&input[start..offset], line));
+            }
+        } else if current.is_none() {
+            current = Some((offset, i + 1));
+        }
+        offset += line.len();
+    }
+
+    if let Some((start, line)) = current {
+        blocks.push(Block::new(&input[start..], line));
+    }
+
+    blocks
+}
+
+/// A block of consecutive non-blank lines, see [`blocks`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The lines of the block, without a trailing line break.
+    pub text: &'a str,
+    /// The line number of the first line of the block in the input.
+    pub line: usize,
+}
+
+impl<'a> Block<'a> {
+    fn new(text: &'a str, line: usize) -> Self {
+        Self {
+            text: text.trim_end_matches(['\r', '\n']),
+            line,
+        }
+    }
+
+    /// Parses every line of the block with `f`, see [`lines`].
+    pub fn lines<T>(
+        &self,
+        f: impl FnMut(&str) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        lines(self.text, f).map_err(|err| err.shifted(self.line - 1))
+    }
+
+    /// Parses every line of the block into a `T`, see [`records`].
+    pub fn records<T>(&self) -> Result<Vec<T>, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        records(self.text).map_err(|err| err.shifted(self.line - 1))
+    }
+}
+
+fn column_of(line: &str, byte_offset: usize) -> usize {
+    line[..byte_offset].chars().count() + 1
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error pointing at the part of the input that could not be parsed. Lines and columns start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn shifted(mut self, lines: usize) -> Self {
+        self.line += lines;
+        self
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{blocks, ints, lines, records, ParseError};
+
+    #[test]
+    fn extracts_ints() {
+        assert_eq!(ints::<i32>("x=1, y=-2..3").unwrap(), [1, -2, 3]);
+        assert_eq!(
+            ints::<i64>("0 3 6 9 12 15\r").unwrap(),
+            [0, 3, 6, 9, 12, 15]
+        );
+        assert_eq!(ints::<i32>("a-b - 5-3").unwrap(), [5, 3]);
+        assert_eq!(ints::<i32>("2-4,6-8").unwrap(), [2, 4, 6, 8]);
+        assert_eq!(ints::<i32>("-1,-2 x-3").unwrap(), [-1, -2, -3]);
+        assert_eq!(ints::<u8>("no numbers").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn reports_invalid_ints() {
+        let err = ints::<u8>("ok 12 300").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 7));
+        assert!(err.message.contains("`300`"));
+
+        let err = ints::<u32>("é -4").unwrap_err();
+        assert_eq!(err.column, 3, "columns count characters");
+    }
+
+    #[test]
+    fn reports_errors_on_their_line() {
+        let input = "1 2\n3 4\n5 x 99999999999\n";
+        let err = lines(input, ints::<i32>).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 5: invalid integer `99999999999`: number too large to fit in target type"
+        );
+        assert_eq!(
+            lines("1 2\n3 4", ints::<i32>),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+    }
+
+    #[test]
+    fn parses_records() {
+        assert_eq!(records::<u32>("1\n2\n3"), Ok(vec![1, 2, 3]));
+
+        let err = records::<u32>("1\n2\nthree").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+        assert!(err.message.starts_with("invalid record `three`"));
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "\na\nb\n\n\nc\r\n\r\nd\ne\n";
+        let blocks = blocks(input);
+        assert_eq!(
+            blocks.iter().map(|b| (b.text, b.line)).collect::<Vec<_>>(),
+            [("a\nb", 2), ("c", 6), ("d\ne", 8)]
+        );
+        assert_eq!(super::blocks("").len(), 0);
+    }
+
+    #[test]
+    fn reports_errors_in_blocks() {
+        let input = "seeds: 1 2\n\n3\n5 -x\n6";
+        let blocks = blocks(input);
+
+        assert_eq!(
+            blocks[1].records::<u32>(),
+            Err(ParseError::new(
+                4,
+                1,
+                "invalid record `5 -x`: invalid digit found in string"
+            ))
+        );
+        let err = blocks[1]
+            .lines(|line| {
+                ints::<i32>(line)?
+                    .into_iter()
+                    .sum::<i32>()
+                    .checked_sub(5)
+                    .filter(|n| *n >= 0)
+                    .ok_or_else(|| ParseError::new(1, 1, "sum too small"))
+            })
+            .unwrap_err();
+        assert_eq!(err.to_string(), "line 3, column 1: sum too small");
+    }
+}
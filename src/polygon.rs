@@ -0,0 +1,180 @@
+//! Measurements of simple polygons on the integer lattice.
+//!
+//! A polygon is given as its vertices in order, either clockwise or counter-clockwise, without repeating the
+//! first vertex at the end. Listing every point of a loop (e.g. every tile of a path through a grid) works as
+//! well as listing only its corners.
+
+use crate::{geom::Point2, math::gcd};
+
+/// Where a point lies in relation to a polygon.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+/// Twice the enclosed area, which is always an integer for lattice polygons (shoelace formula).
+pub fn double_area(vertices: &[Point2<i64>]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| a.x * b.y - b.x * a.y)
+        .sum::<i64>()
+        .abs()
+}
+
+/// The enclosed area.
+pub fn area(vertices: &[Point2<i64>]) -> f64 {
+    double_area(vertices) as f64 / 2.0
+}
+
+/// The number of lattice points on the boundary.
+/// For loops that move orthogonally or diagonally, this is the number of steps around the loop.
+pub fn boundary_length(vertices: &[Point2<i64>]) -> i64 {
+    edges(vertices)
+        .map(|(a, b)| gcd((b.x - a.x).into(), (b.y - a.y).into()) as i64)
+        .sum()
+}
+
+/// The number of lattice points strictly inside of the polygon (Pick's theorem).
+pub fn interior_points(vertices: &[Point2<i64>]) -> i64 {
+    (double_area(vertices) - boundary_length(vertices) + 2) / 2
+}
+
+/// Locates a point inside, outside or on the boundary of a polygon.
+pub fn locate(vertices: &[Point2<i64>], point: Point2<i64>) -> Location {
+    let mut inside = false;
+
+    for (a, b) in edges(vertices) {
+        let (ap, ab) = (point - a, b - a);
+
+        let on_line = ab.x * ap.y - ab.y * ap.x == 0;
+        let in_bounds = point.x >= a.x.min(b.x)
+            && point.x <= a.x.max(b.x)
+            && point.y >= a.y.min(b.y)
+            && point.y <= a.y.max(b.y);
+
+        if on_line && in_bounds {
+            return Location::Boundary;
+        }
+
+        // cast a ray towards positive x and count the edges it crosses.
+        if (a.y > point.y) != (b.y > point.y) {
+            let crosses = if ab.y > 0 {
+                ap.x * ab.y < ap.y * ab.x
+            } else {
+                ap.x * ab.y > ap.y * ab.x
+            };
+            inside ^= crosses;
+        }
+    }
+
+    if inside {
+        Location::Inside
+    } else {
+        Location::Outside
+    }
+}
+
+fn edges(vertices: &[Point2<i64>]) -> impl Iterator<Item = (Point2<i64>, Point2<i64>)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(a, b)| (*a, *b))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        area, boundary_length, double_area, interior_points, locate, Location, Location::*,
+    };
+    use crate::geom::Point2;
+
+    fn polygon(vertices: &[(i64, i64)]) -> Vec<Point2<i64>> {
+        vertices.iter().map(|v| Point2::from(*v)).collect()
+    }
+
+    #[test]
+    fn measures_squares() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 4), (0, 4)]);
+        assert_eq!(double_area(&square), 32);
+        assert_eq!(area(&square), 16.0);
+        assert_eq!(boundary_length(&square), 16);
+        assert_eq!(interior_points(&square), 9);
+
+        let reversed: Vec<_> = square.iter().rev().copied().collect();
+        assert_eq!(double_area(&reversed), 32);
+        assert_eq!(interior_points(&reversed), 9);
+    }
+
+    #[test]
+    fn measures_diagonal_edges() {
+        let triangle = polygon(&[(0, 0), (4, 0), (0, 4)]);
+        assert_eq!(area(&triangle), 8.0);
+        assert_eq!(boundary_length(&triangle), 12);
+        assert_eq!(interior_points(&triangle), 3);
+
+        let sliver = polygon(&[(0, 0), (2, 1), (1, 1)]);
+        assert_eq!(area(&sliver), 0.5);
+        assert_eq!(interior_points(&sliver), 0);
+    }
+
+    #[test]
+    fn measures_loops_of_every_tile() {
+        // the loop of `S-7 / |.| / L-J`, one vertex per tile.
+        let ring = polygon(&[
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+        ]);
+        assert_eq!(boundary_length(&ring), 8);
+        assert_eq!(interior_points(&ring), 1);
+        assert_eq!(locate(&ring, Point2::new(1, 1)), Inside);
+    }
+
+    #[test]
+    fn locates_points() {
+        // an L shape with a concave corner at (2, 2).
+        let shape = polygon(&[(0, 0), (2, 0), (2, 2), (4, 2), (4, 4), (0, 4)]);
+        let cases: [((i64, i64), Location); 8] = [
+            ((1, 1), Inside),
+            ((3, 3), Inside),
+            ((3, 1), Outside),
+            ((5, 3), Outside),
+            ((-1, 2), Outside),
+            ((2, 1), Boundary),
+            ((0, 0), Boundary),
+            ((3, 2), Boundary),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(locate(&shape, Point2::from(point)), expected, "{point:?}");
+        }
+    }
+
+    #[test]
+    fn agrees_with_point_location() {
+        let shape = polygon(&[(0, 0), (6, 0), (6, 5), (3, 2), (0, 5)]);
+        let mut inside = 0;
+        let mut boundary = 0;
+
+        for x in -1..=7 {
+            for y in -1..=6 {
+                match locate(&shape, Point2::new(x, y)) {
+                    Inside => inside += 1,
+                    Boundary => boundary += 1,
+                    Outside => {}
+                }
+            }
+        }
+
+        assert_eq!(interior_points(&shape), inside);
+        assert_eq!(boundary_length(&shape), boundary);
+    }
+}
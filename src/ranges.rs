@@ -0,0 +1,397 @@
+//! Arithmetic on sets of integers that are stored as intervals.
+//!
+//! ```
+//! use advent_of_code::ranges::{Interval, RangeSet};
+//!
+//! let seeds: RangeSet<u64> = [79..93, 55..68].into_iter().collect();
+//! // move 50..98 to 52..100.
+//! let soil = seeds.map(&[(Interval::from(50..98), 52)]);
+//!
+//! assert_eq!(soil.iter().collect::<Vec<_>>(), [Interval::new(57, 70), Interval::new(81, 95)]);
+//! ```
+
+use std::{
+    fmt::Display,
+    ops::{Add, Range, Sub},
+};
+
+/// A half-open interval `start..end` of integers. Intervals with `end <= start` are empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: Copy + Ord> Interval<T> {
+    pub const fn new(start: T, end: T) -> Self {
+        Self { start, end }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values contained in both intervals, [`None`] if there are none.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Splits the interval into the values below `at` and the values from `at` onwards.
+    pub fn split_at(&self, at: T) -> (Option<Self>, Option<Self>) {
+        let below = Self::new(self.start, self.end.min(at));
+        let above = Self::new(self.start.max(at), self.end);
+        (
+            (!below.is_empty()).then_some(below),
+            (!above.is_empty()).then_some(above),
+        )
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Default> Interval<T> {
+    /// The number of values in the interval.
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::default()
+        } else {
+            self.end - self.start
+        }
+    }
+}
+
+impl<T> From<Range<T>> for Interval<T> {
+    fn from(range: Range<T>) -> Self {
+        Self {
+            start: range.start,
+            end: range.end,
+        }
+    }
+}
+
+impl<T: Display> Display for Interval<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of integers, stored as sorted intervals that neither overlap nor touch each other.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RangeSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self { intervals: vec![] }
+    }
+
+    fn normalized(mut intervals: Vec<Interval<T>>) -> Self {
+        intervals.retain(|i| !i.is_empty());
+        intervals.sort_unstable_by_key(|i| i.start);
+
+        let mut merged: Vec<Interval<T>> = Vec::with_capacity(intervals.len());
+        for interval in intervals {
+            match merged.last_mut() {
+                Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+                _ => merged.push(interval),
+            }
+        }
+
+        Self { intervals: merged }
+    }
+
+    /// The intervals of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.intervals.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The smallest value of the set.
+    pub fn min(&self) -> Option<T> {
+        self.intervals.first().map(|i| i.start)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(i).is_some_and(|i| i.contains(value))
+    }
+
+    /// Adds all values of an interval to the set.
+    pub fn insert(&mut self, interval: impl Into<Interval<T>>) {
+        let mut intervals = std::mem::take(&mut self.intervals);
+        intervals.push(interval.into());
+        *self = Self::normalized(intervals);
+    }
+
+    /// All values that are in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalized(self.iter().chain(other.iter()).collect())
+    }
+
+    /// All values that are in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.intervals.get(i), other.intervals.get(j)) {
+            intervals.extend(a.intersection(b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { intervals }
+    }
+
+    /// All values of this set that are not in `other`.
+    pub fn subtract(&self, other: &Self) -> Self {
+        let mut intervals = vec![];
+        let mut j = 0;
+
+        for interval in self.iter() {
+            let mut rest = Some(interval);
+            // skip intervals of `other` that end before this one starts.
+            while other
+                .intervals
+                .get(j)
+                .is_some_and(|b| b.end <= interval.start)
+            {
+                j += 1;
+            }
+
+            for cut in other.intervals[j..]
+                .iter()
+                .take_while(|b| b.start < interval.end)
+            {
+                let Some(remaining) = rest else { break };
+                let (below, _) = remaining.split_at(cut.start);
+                let (_, above) = remaining.split_at(cut.end);
+                intervals.extend(below);
+                rest = above;
+            }
+
+            intervals.extend(rest);
+        }
+
+        Self { intervals }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> RangeSet<T> {
+    /// Applies a piecewise mapping to every value of the set. Each rule `(source, target)` moves the values
+    /// of `source` so that `source.start` lands on `target`. Values that no rule covers stay in place.
+    /// If rules overlap, the first matching rule wins.
+    pub fn map(&self, rules: &[(Interval<T>, T)]) -> Self {
+        let mut unmapped = self.clone();
+        let mut mapped = vec![];
+
+        for (source, target) in rules {
+            let covered = unmapped.intersection(&Self::from(*source));
+            mapped.extend(covered.iter().map(|i| {
+                Interval::new(
+                    i.start - source.start + *target,
+                    i.end - source.start + *target,
+                )
+            }));
+            unmapped = unmapped.subtract(&covered);
+        }
+
+        mapped.extend(unmapped.intervals);
+        Self::normalized(mapped)
+    }
+}
+
+impl<T: Copy + Ord + Sub<Output = T> + Add<Output = T> + Default> RangeSet<T> {
+    /// The number of values in the set.
+    pub fn count(&self) -> T {
+        self.iter().fold(T::default(), |sum, i| sum + i.len())
+    }
+}
+
+impl<T: Copy + Ord> From<Interval<T>> for RangeSet<T> {
+    fn from(interval: Interval<T>) -> Self {
+        Self::normalized(vec![interval])
+    }
+}
+
+impl<T: Copy + Ord, I: Into<Interval<T>>> FromIterator<I> for RangeSet<T> {
+    fn from_iter<It: IntoIterator<Item = I>>(iter: It) -> Self {
+        Self::normalized(iter.into_iter().map(Into::into).collect())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{Interval, RangeSet};
+    use std::collections::BTreeSet;
+
+    /// All random sets are drawn from this universe.
+    const MAX: i64 = 64;
+
+    /// A xorshift generator, random enough to find edge cases without pulling in a crate.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn below(&mut self, n: i64) -> i64 {
+            (self.next() % n as u64) as i64
+        }
+
+        fn interval(&mut self) -> Interval<i64> {
+            let start = self.below(MAX);
+            Interval::new(start, start + self.below(12) - 2)
+        }
+
+        fn range_set(&mut self) -> (RangeSet<i64>, BTreeSet<i64>) {
+            let intervals: Vec<_> = (0..self.below(6)).map(|_| self.interval()).collect();
+            let brute_force = intervals.iter().flat_map(|i| i.start..i.end).collect();
+            (intervals.into_iter().collect(), brute_force)
+        }
+    }
+
+    fn values(set: &RangeSet<i64>) -> BTreeSet<i64> {
+        set.iter().flat_map(|i| i.start..i.end).collect()
+    }
+
+    fn assert_normalized(set: &RangeSet<i64>) {
+        assert!(set.iter().all(|i| !i.is_empty()), "{set:?}");
+        assert!(
+            set.intervals.windows(2).all(|w| w[0].end < w[1].start),
+            "{set:?}"
+        );
+    }
+
+    #[test]
+    fn splits_intervals() {
+        let interval = Interval::new(2, 8);
+        assert_eq!(
+            interval.split_at(5),
+            (Some(Interval::new(2, 5)), Some(Interval::new(5, 8)))
+        );
+        assert_eq!(interval.split_at(2), (None, Some(interval)));
+        assert_eq!(interval.split_at(9), (Some(interval), None));
+        assert_eq!(interval.intersection(&Interval::new(8, 10)), None);
+        assert_eq!(interval.len(), 6);
+        assert_eq!(Interval::new(3, 1).len(), 0);
+    }
+
+    #[test]
+    fn merges_intervals() {
+        let mut set: RangeSet<u32> = [5..8, 1..3, 2..4, 8..9, 12..12].into_iter().collect();
+        assert_eq!(
+            set.iter().map(|i| i.to_string()).collect::<Vec<_>>(),
+            ["1..4", "5..9"]
+        );
+        set.insert(4..5);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [Interval::new(1, 9)]);
+        assert_eq!(set.count(), 8);
+        assert_eq!(set.min(), Some(1));
+    }
+
+    #[test]
+    fn maps_offsets() {
+        // the seed-to-soil example of 2023 day 5.
+        let seeds: RangeSet<u64> = [79..93, 55..68].into_iter().collect();
+        let rules = [(Interval::new(98, 100), 50), (Interval::new(50, 98), 52)];
+        let soil = seeds.map(&rules);
+        assert_eq!(
+            soil.iter().collect::<Vec<_>>(),
+            [Interval::new(57, 70), Interval::new(81, 95)]
+        );
+
+        // values outside of all rules stay in place, parts of an interval can move apart.
+        let set = RangeSet::from(Interval::new(0u64, 10));
+        let moved = set.map(&[(Interval::new(3, 5), 20)]);
+        assert_eq!(
+            moved.iter().collect::<Vec<_>>(),
+            [
+                Interval::new(0, 3),
+                Interval::new(5, 10),
+                Interval::new(20, 22)
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..2000 {
+            let (a, a_values) = rng.range_set();
+            let (b, b_values) = rng.range_set();
+
+            assert_normalized(&a);
+            assert_eq!(values(&a), a_values);
+            assert_eq!(a.count(), a_values.len() as i64);
+            for x in -2..MAX + 12 {
+                assert_eq!(a.contains(x), a_values.contains(&x), "{a:?} contains {x}");
+            }
+
+            let union = a.union(&b);
+            assert_normalized(&union);
+            assert_eq!(values(&union), &a_values | &b_values);
+
+            let intersection = a.intersection(&b);
+            assert_normalized(&intersection);
+            assert_eq!(values(&intersection), &a_values & &b_values);
+
+            let difference = a.subtract(&b);
+            assert_normalized(&difference);
+            assert_eq!(values(&difference), &a_values - &b_values);
+
+            let mut inserted = a.clone();
+            let interval = rng.interval();
+            inserted.insert(interval);
+            assert_normalized(&inserted);
+            assert_eq!(
+                values(&inserted),
+                &a_values | &(interval.start..interval.end).collect()
+            );
+        }
+    }
+
+    #[test]
+    fn matches_brute_force_for_mappings() {
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..2000 {
+            let (set, set_values) = rng.range_set();
+            let rules: Vec<_> = (0..rng.below(4))
+                .map(|_| (rng.interval(), rng.below(MAX)))
+                .collect();
+
+            let mapped = set.map(&rules);
+            assert_normalized(&mapped);
+
+            let expected: BTreeSet<i64> = set_values
+                .iter()
+                .map(|x| {
+                    rules
+                        .iter()
+                        .find(|(source, _)| source.contains(*x))
+                        .map_or(*x, |(source, target)| x - source.start + target)
+                })
+                .collect();
+            assert_eq!(values(&mapped), expected, "{set:?} mapped by {rules:?}");
+        }
+    }
+}
@@ -0,0 +1,165 @@
+//! Runs the solutions in `src/bin` for the `all` and `time` commands.
+//!
+//! The solutions are compiled into this binary by `build.rs`, and every day runs in a child process of it.
+//! They are kept out of the main binary, so a day that does not compile only breaks the commands that run all days.
+use advent_of_code::template::commands::{all, run, time};
+use args::{parse, AppArguments};
+
+/// The solutions in `src/bin`, compiled into this binary by `build.rs`.
+/// Test builds leave them out, their tests run as part of the solution binaries.
+#[cfg(not(test))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+#[cfg(test)]
+mod solutions {
+    pub fn registry() -> advent_of_code::template::registry::Registry {
+        Default::default()
+    }
+}
+
+mod args {
+    use advent_of_code::template::{
+        args::{parse_iterations, parse_puzzle, parse_year_and_day},
+        runner::RunOptions,
+        Day, PuzzleId, Year,
+    };
+    use std::{error::Error, path::PathBuf, process, time::Duration};
+
+    pub enum AppArguments {
+        All {
+            year: Option<Year>,
+            timeout: Option<Duration>,
+            jobs: Option<usize>,
+        },
+        Time {
+            all: bool,
+            year: Option<Year>,
+            day: Option<Day>,
+            store: bool,
+            options: RunOptions,
+            compare: Option<f64>,
+            timeout: Option<Duration>,
+        },
+        /// Runs a single day in this process, used by `all` and `time` for each day.
+        Run {
+            puzzle: PuzzleId,
+            options: RunOptions,
+        },
+    }
+
+    /// Parses the `--timeout <seconds>` option of the commands that run several days.
+    fn parse_timeout(args: &mut pico_args::Arguments) -> Result<Option<Duration>, Box<dyn Error>> {
+        let timeout: Option<u64> = args.opt_value_from_str("--timeout")?;
+        Ok(timeout.map(Duration::from_secs))
+    }
+
+    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_env();
+
+        let app_args = match args.subcommand()?.as_deref() {
+            Some("all") => AppArguments::All {
+                year: Year::unless_configured(args.opt_value_from_str("--year")?),
+                timeout: parse_timeout(&mut args)?,
+                jobs: args
+                    .opt_value_from_str::<_, usize>("--jobs")?
+                    .map(|jobs| jobs.max(1)),
+            },
+            Some("time") => {
+                let all = args.contains("--all");
+                let store = args.contains("--store");
+                let year: Option<Year> = args.opt_value_from_str("--year")?;
+                let budget: Option<u64> = args.opt_value_from_str("--budget")?;
+                let iterations = parse_iterations(&mut args)?;
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let timeout = parse_timeout(&mut args)?;
+                let (positional_year, day) = parse_year_and_day(&mut args)?;
+
+                AppArguments::Time {
+                    all,
+                    year: Year::unless_configured(year.or(positional_year)),
+                    day,
+                    store,
+                    options: RunOptions {
+                        budget: budget.map(Duration::from_millis),
+                        iterations,
+                        ..RunOptions::default()
+                    },
+                    compare: compare.then(|| threshold.unwrap_or(10.0)),
+                    timeout,
+                }
+            }
+            Some("run") => {
+                let options = RunOptions {
+                    time: args.contains("--time"),
+                    compare: args.contains("--compare"),
+                    records: args.opt_value_from_str::<_, PathBuf>("--records")?,
+                    budget: args
+                        .opt_value_from_str("--budget")?
+                        .map(Duration::from_millis),
+                    iterations: parse_iterations(&mut args)?,
+                    ..RunOptions::default()
+                };
+
+                AppArguments::Run {
+                    puzzle: parse_puzzle(&mut args)?,
+                    options,
+                }
+            }
+            Some(x) => {
+                eprintln!("Unknown command: {x}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!("No command specified.");
+                process::exit(1);
+            }
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        }
+
+        Ok(app_args)
+    }
+}
+
+fn main() {
+    match parse() {
+        Err(err) => {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        }
+        Ok(args) => match args {
+            AppArguments::All {
+                year,
+                timeout,
+                jobs,
+            } => all::handle(&solutions::registry(), year, timeout, jobs),
+            AppArguments::Time {
+                day,
+                year,
+                all,
+                store,
+                options,
+                compare,
+                timeout,
+            } => time::handle(
+                &solutions::registry(),
+                year,
+                day,
+                all,
+                store,
+                options,
+                compare,
+                timeout,
+            ),
+            AppArguments::Run { puzzle, options } => {
+                run::handle(&solutions::registry(), puzzle, &options)
+            }
+        },
+    };
+}
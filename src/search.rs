@@ -0,0 +1,348 @@
+//! Shortest path searches over implicit graphs.
+//!
+//! Nodes can be any hashable value, e.g. a position or a `(position, direction, streak)` tuple, and their
+//! neighbours are produced on demand by a closure. A search can be stopped early once it reaches a goal.
+//!
+//! ```
+//! use advent_of_code::search::Search;
+//!
+//! // walk a number line in steps of +3 and -2.
+//! let paths = Search::new([0])
+//!     .track_paths()
+//!     .bfs(|n: &i32| [n + 3, n - 2], |n| *n == 7);
+//!
+//! assert_eq!(paths.goal_distance(), Some(4));
+//! assert_eq!(paths.goal_path(), Some(vec![0, 3, 6, 9, 7]));
+//! ```
+
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Configures a search from one or more start nodes.
+pub struct Search<N> {
+    starts: Vec<N>,
+    track_paths: bool,
+}
+
+impl<N: Clone + Eq + Hash> Search<N> {
+    /// Starts a search from the given nodes, all of which have a distance of zero.
+    pub fn new(starts: impl IntoIterator<Item = N>) -> Self {
+        Self {
+            starts: starts.into_iter().collect(),
+            track_paths: false,
+        }
+    }
+
+    /// Records the predecessor of every node so that paths can be reconstructed.
+    pub fn track_paths(mut self) -> Self {
+        self.track_paths = true;
+        self
+    }
+
+    /// Breadth-first search over unweighted edges.
+    /// Stops as soon as a node matching `is_goal` is reached, pass `|_| false` to explore the whole graph.
+    pub fn bfs<I>(
+        self,
+        mut neighbours: impl FnMut(&N) -> I,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Paths<N, usize>
+    where
+        I: IntoIterator<Item = N>,
+    {
+        let mut paths = self.init_paths();
+        let mut queue: VecDeque<N> = self.starts.into_iter().collect();
+
+        while let Some(node) = queue.pop_front() {
+            if is_goal(&node) {
+                paths.goal = Some(node);
+                break;
+            }
+
+            let distance = paths.distances[&node] + 1;
+
+            for next in neighbours(&node) {
+                if !paths.distances.contains_key(&next) {
+                    paths.relax(&node, next.clone(), distance);
+                    queue.push_back(next);
+                }
+            }
+        }
+
+        paths
+    }
+
+    /// Dijkstra's algorithm over edges with non-negative costs.
+    /// Stops as soon as a node matching `is_goal` is settled, pass `|_| false` to explore the whole graph.
+    pub fn dijkstra<C, I>(
+        self,
+        neighbours: impl FnMut(&N) -> I,
+        is_goal: impl FnMut(&N) -> bool,
+    ) -> Paths<N, C>
+    where
+        C: Copy + Ord + Default + Add<Output = C>,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        self.astar(neighbours, |_| C::default(), is_goal)
+    }
+
+    /// A* search over edges with non-negative costs.
+    /// `heuristic` estimates the remaining cost to a goal and must never overestimate it.
+    pub fn astar<C, I>(
+        self,
+        mut neighbours: impl FnMut(&N) -> I,
+        mut heuristic: impl FnMut(&N) -> C,
+        mut is_goal: impl FnMut(&N) -> bool,
+    ) -> Paths<N, C>
+    where
+        C: Copy + Ord + Default + Add<Output = C>,
+        I: IntoIterator<Item = (N, C)>,
+    {
+        let mut paths = self.init_paths();
+        let mut queue: BinaryHeap<Candidate<N, C>> = self
+            .starts
+            .into_iter()
+            .map(|node| Candidate {
+                estimate: heuristic(&node),
+                cost: C::default(),
+                node,
+            })
+            .collect();
+
+        while let Some(Candidate { cost, node, .. }) = queue.pop() {
+            // skip stale queue entries of nodes that were reached more cheaply since.
+            if paths.distances.get(&node).is_some_and(|best| cost > *best) {
+                continue;
+            }
+
+            if is_goal(&node) {
+                paths.goal = Some(node);
+                break;
+            }
+
+            for (next, step) in neighbours(&node) {
+                let next_cost = cost + step;
+                if paths
+                    .distances
+                    .get(&next)
+                    .is_none_or(|best| next_cost < *best)
+                {
+                    paths.relax(&node, next.clone(), next_cost);
+                    queue.push(Candidate {
+                        estimate: next_cost + heuristic(&next),
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+
+        paths
+    }
+
+    fn init_paths<C: Default>(&self) -> Paths<N, C> {
+        Paths {
+            distances: self
+                .starts
+                .iter()
+                .map(|node| (node.clone(), C::default()))
+                .collect(),
+            predecessors: self.track_paths.then(HashMap::new),
+            goal: None,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The result of a [`Search`].
+#[derive(Debug, Clone)]
+pub struct Paths<N, C> {
+    /// The distance of every reached node. If the search stopped at a goal, distances of nodes
+    /// further away than the goal may not be minimal yet.
+    pub distances: HashMap<N, C>,
+    /// The node every reached node was reached from, if paths were tracked.
+    pub predecessors: Option<HashMap<N, N>>,
+    /// The goal the search stopped at, if any.
+    pub goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Paths<N, C> {
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn goal_distance(&self) -> Option<C> {
+        self.distance(self.goal.as_ref()?)
+    }
+
+    /// The path from a start node to `node`, both inclusive.
+    /// Returns [`None`] if the node was not reached or paths were not tracked.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        let predecessors = self.predecessors.as_ref()?;
+        if !self.distances.contains_key(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        while let Some(previous) = predecessors.get(path.last().unwrap()) {
+            path.push(previous.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+
+    fn relax(&mut self, from: &N, to: N, distance: C) {
+        if let Some(predecessors) = &mut self.predecessors {
+            predecessors.insert(to.clone(), from.clone());
+        }
+        self.distances.insert(to, distance);
+    }
+}
+
+/// A queue entry ordered by its estimated total cost, lowest first.
+struct Candidate<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Candidate<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Candidate<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Candidate<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Candidate<N, C> {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::Search;
+    use crate::{
+        geom::{Dir4, Point2},
+        grid::{Grid, Pos},
+    };
+
+    fn get_mock_maze() -> Grid<char> {
+        ["S..#....", ".#.#.##.", ".#...#..", ".####.#.", "......#E"]
+            .join("\n")
+            .parse()
+            .unwrap()
+    }
+
+    fn open_neighbours(grid: &Grid<char>, pos: Pos) -> Vec<Pos> {
+        grid.neighbours4(pos).filter(|n| grid[*n] != '#').collect()
+    }
+
+    #[test]
+    fn finds_shortest_paths_with_bfs() {
+        let grid = get_mock_maze();
+        let end = grid.find(|c| *c == 'E').unwrap();
+
+        let paths = Search::new([(0, 0)])
+            .track_paths()
+            .bfs(|pos| open_neighbours(&grid, *pos), |pos| *pos == end);
+
+        assert_eq!(paths.goal, Some(end));
+        assert_eq!(paths.goal_distance(), Some(15));
+
+        let path = paths.goal_path().unwrap();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert!(path
+            .windows(2)
+            .all(|w| grid.neighbours4(w[0]).any(|n| n == w[1])));
+    }
+
+    #[test]
+    fn explores_whole_graphs() {
+        let grid = get_mock_maze();
+        let paths = Search::new([(0, 0)]).bfs(|pos| open_neighbours(&grid, *pos), |_| false);
+
+        assert_eq!(paths.goal, None);
+        assert_eq!(paths.distances.len(), grid.find_all(|c| *c != '#').count());
+        assert_eq!(paths.distance(&(2, 0)), Some(2));
+        assert_eq!(paths.path_to(&(2, 0)), None, "paths are not tracked");
+    }
+
+    #[test]
+    fn searches_from_several_starts() {
+        let paths = Search::new([0, 10]).bfs(|n: &i32| [n - 1, n + 1], |n| *n == 8);
+        assert_eq!(paths.goal_distance(), Some(2));
+    }
+
+    #[test]
+    fn finds_cheapest_paths_with_dijkstra() {
+        // the direct edge is more expensive than the detour.
+        let edges = |n: &char| match n {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 2)],
+            'c' => vec![('d', 3)],
+            _ => vec![],
+        };
+
+        let paths = Search::new(['a']).track_paths().dijkstra(edges, |_| false);
+        assert_eq!(paths.distance(&'d'), Some(6));
+        assert_eq!(paths.path_to(&'d'), Some(vec!['a', 'b', 'c', 'd']));
+        assert_eq!(paths.distance(&'e'), None);
+    }
+
+    #[test]
+    fn supports_stateful_nodes() {
+        // digits are heat losses, moves may go at most 3 tiles straight and never reverse.
+        let grid = Grid::parse("2413\n3215\n3255", |c| c.to_digit(10).unwrap()).unwrap();
+        let end = (grid.width() - 1, grid.height() - 1);
+
+        type Node = (Pos, Dir4, u8);
+        let moves = |&(pos, dir, streak): &Node| {
+            [dir, dir.turn_left(), dir.turn_right()]
+                .into_iter()
+                .filter(move |next| *next != dir || streak < 3)
+                .filter_map(|next| {
+                    let next_pos = grid.step(pos, next)?;
+                    let streak = if next == dir { streak + 1 } else { 1 };
+                    Some(((next_pos, next, streak), grid[next_pos]))
+                })
+                .collect::<Vec<_>>()
+        };
+        let starts = [((0, 0), Dir4::East, 0), ((0, 0), Dir4::South, 0)];
+
+        let dijkstra = Search::new(starts).dijkstra(moves, |(pos, ..)| *pos == end);
+        assert_eq!(dijkstra.goal_distance(), Some(16));
+
+        let astar = Search::new(starts).track_paths().astar(
+            moves,
+            |(pos, ..)| Point2::from(*pos).manhattan(Point2::from(end)) as u32,
+            |(pos, ..)| *pos == end,
+        );
+        assert_eq!(astar.goal_distance(), Some(16));
+
+        let path = astar.goal_path().unwrap();
+        let cost: u32 = path.iter().skip(1).map(|(pos, ..)| grid[*pos]).sum();
+        assert_eq!(cost, 16);
+    }
+}
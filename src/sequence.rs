@@ -0,0 +1,193 @@
+//! Extrapolating integer sequences that are described by a polynomial.
+//!
+//! A sequence is fitted with Newton's forward differences: `f(x) = Σ Δʲf(0) · C(x, j)`. The binomial
+//! coefficients `C(x, j)` are integers for every integer `x`, so values are computed exactly on `i128`.
+
+use std::{error::Error, fmt::Display};
+
+/// A polynomial through the values of a sequence, where the first value lies at `x = 0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polynomial {
+    /// The first value of every row of the difference table, `Δʲf(0)` for `j` up to the degree.
+    differences: Vec<i128>,
+}
+
+impl Polynomial {
+    /// Fits a polynomial through a sequence. The sequence is only accepted as polynomial of degree `d` if its
+    /// `d + 1`-th differences are all zero, which needs at least `d + 2` values.
+    pub fn fit<T: Copy + Into<i128>>(values: &[T]) -> Result<Self, SequenceError> {
+        if values.is_empty() {
+            return Err(SequenceError::Empty);
+        }
+
+        let mut row: Vec<i128> = values.iter().map(|v| (*v).into()).collect();
+        let mut differences = vec![];
+
+        while row.iter().any(|v| *v != 0) {
+            if row.len() == 1 {
+                return Err(SequenceError::NotPolynomial { len: values.len() });
+            }
+            differences.push(row[0]);
+            row = row
+                .windows(2)
+                .map(|w| w[1].checked_sub(w[0]))
+                .collect::<Option<_>>()
+                .ok_or(SequenceError::Overflow)?;
+        }
+
+        Ok(Self { differences })
+    }
+
+    /// The degree of the polynomial. The zero sequence has degree 0 as well.
+    pub fn degree(&self) -> usize {
+        self.differences.len().saturating_sub(1)
+    }
+
+    /// The value at `x`, where `0` is the first value of the fitted sequence and negative `x` lie before it.
+    pub fn at(&self, x: i128) -> Result<i128, SequenceError> {
+        let mut value: i128 = 0;
+        // C(x, j), starting with C(x, 0) = 1.
+        let mut binomial: i128 = 1;
+
+        for (j, difference) in (0..).zip(&self.differences) {
+            if j > 0 {
+                // C(x, j) = C(x, j - 1) · (x - j + 1) / j, the division is always exact.
+                binomial = x
+                    .checked_sub(j - 1)
+                    .and_then(|factor| binomial.checked_mul(factor))
+                    .ok_or(SequenceError::Overflow)?
+                    / j;
+            }
+            value = difference
+                .checked_mul(binomial)
+                .and_then(|term| value.checked_add(term))
+                .ok_or(SequenceError::Overflow)?;
+        }
+
+        Ok(value)
+    }
+}
+
+/// The value `steps` positions after the last value of a sequence.
+pub fn forward<T: Copy + Into<i128>>(values: &[T], steps: usize) -> Result<i128, SequenceError> {
+    let polynomial = Polynomial::fit(values)?;
+    polynomial.at((values.len() - 1) as i128 + steps as i128)
+}
+
+/// The value `steps` positions before the first value of a sequence.
+pub fn backward<T: Copy + Into<i128>>(values: &[T], steps: usize) -> Result<i128, SequenceError> {
+    Polynomial::fit(values)?.at(-(steps as i128))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when extrapolating a sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceError {
+    /// The sequence does not contain any values.
+    Empty,
+    /// The differences of the sequence do not reach zero within its length.
+    NotPolynomial { len: usize },
+    /// A difference or an extrapolated value does not fit into an `i128`.
+    Overflow,
+}
+
+impl Error for SequenceError {}
+
+impl Display for SequenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SequenceError::Empty => write!(f, "sequence is empty"),
+            SequenceError::NotPolynomial { len } => write!(
+                f,
+                "sequence of {len} values is not described by a polynomial of degree below {}",
+                len.saturating_sub(1)
+            ),
+            SequenceError::Overflow => write!(f, "value does not fit into an i128"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use super::{backward, forward, Polynomial, SequenceError};
+
+    fn cubic(x: i128) -> i128 {
+        2 * x * x * x - 3 * x * x + 5 * x - 7
+    }
+
+    #[test]
+    fn extrapolates_puzzle_sequences() {
+        assert_eq!(forward(&[0, 3, 6, 9, 12, 15], 1), Ok(18));
+        assert_eq!(forward(&[1, 3, 6, 10, 15, 21], 1), Ok(28));
+        assert_eq!(forward(&[10, 13, 16, 21, 30, 45], 1), Ok(68));
+        assert_eq!(backward(&[0, 3, 6, 9, 12, 15], 1), Ok(-3));
+        assert_eq!(backward(&[1, 3, 6, 10, 15, 21], 1), Ok(0));
+        assert_eq!(backward(&[10, 13, 16, 21, 30, 45], 1), Ok(5));
+    }
+
+    #[test]
+    fn fits_degrees() {
+        assert_eq!(Polynomial::fit(&[0, 0]).unwrap().degree(), 0);
+        assert_eq!(Polynomial::fit(&[4, 4, 4]).unwrap().degree(), 0);
+        assert_eq!(Polynomial::fit(&[1, 3, 5]).unwrap().degree(), 1);
+
+        let values: Vec<i128> = (0..6).map(cubic).collect();
+        assert_eq!(Polynomial::fit(&values).unwrap().degree(), 3);
+    }
+
+    #[test]
+    fn extrapolates_many_steps() {
+        let values: Vec<i128> = (0..5).map(cubic).collect();
+        let polynomial = Polynomial::fit(&values).unwrap();
+
+        for x in -100..100 {
+            assert_eq!(polynomial.at(x), Ok(cubic(x)), "x = {x}");
+        }
+        assert_eq!(forward(&values, 1000), Ok(cubic(1004)));
+        assert_eq!(backward(&values, 1000), Ok(cubic(-1000)));
+    }
+
+    #[test]
+    fn accepts_narrow_integer_types() {
+        assert_eq!(forward(&[1u8, 4, 9, 16], 2), Ok(36));
+        assert_eq!(backward(&[-1i32, -2, -3], 2), Ok(1));
+    }
+
+    #[test]
+    fn rejects_non_polynomial_sequences() {
+        assert_eq!(
+            Polynomial::fit(&[1, 2, 4, 8, 16, 32]),
+            Err(SequenceError::NotPolynomial { len: 6 })
+        );
+        // a cubic needs 5 values to be confirmed.
+        let values: Vec<i128> = (0..4).map(cubic).collect();
+        assert_eq!(
+            forward(&values, 1),
+            Err(SequenceError::NotPolynomial { len: 4 })
+        );
+        assert_eq!(
+            forward(&[5], 1),
+            Err(SequenceError::NotPolynomial { len: 1 })
+        );
+        assert_eq!(forward::<i32>(&[], 1), Err(SequenceError::Empty));
+    }
+
+    #[test]
+    fn reports_overflows() {
+        assert_eq!(
+            forward(&[i128::MIN, i128::MAX], 1),
+            Err(SequenceError::Overflow)
+        );
+
+        let step = i128::MAX / 4;
+        assert_eq!(forward(&[0, step, 2 * step], 1), Ok(3 * step));
+        assert_eq!(forward(&[7, 7], i64::MAX as usize), Ok(7));
+        assert_eq!(
+            forward(&[0, step, 2 * step], 3),
+            Err(SequenceError::Overflow)
+        );
+    }
+}
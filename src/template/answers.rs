@@ -0,0 +1,465 @@
+use std::{collections::HashMap, fmt::Display, fs, io::Error, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, Day, Year};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// The verdict the server gave for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+}
+
+impl Verdict {
+    fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+        }
+    }
+
+    /// Reads the verdict from the server's reply to a submitted answer.
+    /// Replies such as rate limits or already solved parts do not carry a verdict.
+    pub fn from_response(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if message.contains("That's not the right answer") {
+            if message.contains("too high") {
+                Some(Verdict::TooHigh)
+            } else if message.contains("too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.as_str().replace('_', " "))
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Verdict::Correct),
+            "wrong" => Ok(Verdict::Wrong),
+            "too_high" => Ok(Verdict::TooHigh),
+            "too_low" => Ok(Verdict::TooLow),
+            _ => Err(format!("Unknown verdict `{s}`.")),
+        }
+    }
+}
+
+/// A submitted answer for one part of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// Reasons for refusing to submit an answer.
+#[derive(Debug, PartialEq)]
+pub enum Rejection {
+    AlreadyCorrect,
+    KnownWrong(Verdict),
+    DiffersFromCorrect(String),
+    NotBelow(String),
+    NotAbove(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadyCorrect => write!(f, "this answer was already accepted."),
+            Rejection::KnownWrong(verdict) => {
+                write!(f, "this answer was already submitted ({verdict}).")
+            }
+            Rejection::DiffersFromCorrect(correct) => {
+                write!(f, "the correct answer `{correct}` is already known.")
+            }
+            Rejection::NotBelow(bound) => write!(f, "`{bound}` was already too high."),
+            Rejection::NotAbove(bound) => write!(f, "`{bound}` was already too low."),
+        }
+    }
+}
+
+/// Ledger of every submitted answer.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of an event.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let path = get_file_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of an event. If not present, returns an empty ledger.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let Ok(s) = fs::read_to_string(get_file_path(year)) else {
+            return Answers::default();
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    /// Record an answer, replacing an earlier entry for the same answer.
+    pub fn record(&mut self, answer: Answer) {
+        let answer = Answer {
+            answer: answer.answer.trim().to_string(),
+            ..answer
+        };
+        self.data.retain(|a| {
+            !(a.day == answer.day && a.part == answer.part && a.answer.trim() == answer.answer)
+        });
+        self.data.push(answer);
+    }
+
+    /// Returns the accepted answer for a part if it is known.
+    pub fn correct_answer(&self, day: Day, part: u8) -> Option<&str> {
+        self.data
+            .iter()
+            .find(|a| a.day == day && a.part == part && a.verdict == Verdict::Correct)
+            .map(|a| a.answer.as_str())
+    }
+
+    /// Check whether submitting an answer could be successful given previous submissions.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Rejection> {
+        let submitted = self.data.iter().filter(|a| a.day == day && a.part == part);
+        let answer = answer.trim();
+        let value = answer.parse::<i128>().ok();
+
+        for previous in submitted {
+            if previous.answer.trim() == answer {
+                return Err(match previous.verdict {
+                    Verdict::Correct => Rejection::AlreadyCorrect,
+                    verdict => Rejection::KnownWrong(verdict),
+                });
+            }
+
+            let previous_value = previous.answer.trim().parse::<i128>().ok();
+
+            match (previous.verdict, value, previous_value) {
+                (Verdict::Correct, _, _) => {
+                    return Err(Rejection::DiffersFromCorrect(previous.answer.clone()));
+                }
+                (Verdict::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    return Err(Rejection::NotBelow(previous.answer.clone()));
+                }
+                (Verdict::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    return Err(Rejection::NotAbove(previous.answer.clone()));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn get_file_path(year: Option<Year>) -> PathBuf {
+    data_dir(year).join(ANSWERS_FILE_NAME)
+}
+
+/// Returns a warning for answers that are very likely not what the puzzle is asking for.
+pub fn suspicious(answer: &str) -> Option<&'static str> {
+    let answer = answer.trim();
+
+    if answer.is_empty() {
+        Some("the answer is empty.")
+    } else if answer == "0" {
+        Some("the answer is zero.")
+    } else if answer.parse::<i128>().is_ok_and(|x| x < 0) {
+        Some("the answer is negative.")
+    } else {
+        None
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .ok_or("Expected answer.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected answer.verdict to be a string.")?
+            .parse()?;
+
+        Ok(Answer {
+            day,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{suspicious, Answer, Answers, Rejection, Verdict};
+    use crate::day;
+
+    fn get_mock_answers() -> Answers {
+        Answers {
+            data: vec![
+                Answer {
+                    day: day!(1),
+                    part: 1,
+                    answer: "100".into(),
+                    verdict: Verdict::TooHigh,
+                },
+                Answer {
+                    day: day!(1),
+                    part: 1,
+                    answer: "20".into(),
+                    verdict: Verdict::TooLow,
+                },
+                Answer {
+                    day: day!(1),
+                    part: 1,
+                    answer: "50".into(),
+                    verdict: Verdict::Wrong,
+                },
+                Answer {
+                    day: day!(2),
+                    part: 2,
+                    answer: "abc".into(),
+                    verdict: Verdict::Correct,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(day!(1), 1, "50"),
+            Err(Rejection::KnownWrong(Verdict::Wrong))
+        );
+    }
+
+    #[test]
+    fn ignores_surrounding_whitespace() {
+        let mut answers = get_mock_answers();
+        assert_eq!(
+            answers.check(day!(1), 1, "50\n"),
+            Err(Rejection::KnownWrong(Verdict::Wrong))
+        );
+
+        answers.record(Answer {
+            day: day!(1),
+            part: 1,
+            answer: " 50\n".into(),
+            verdict: Verdict::Wrong,
+        });
+        assert_eq!(answers.data.len(), 4);
+        assert_eq!(answers.data[3].answer, "50");
+    }
+
+    #[test]
+    fn reads_verdicts_from_responses() {
+        let verdict = Verdict::from_response;
+        assert_eq!(
+            verdict("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too high."),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            verdict("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            verdict("That's not the right answer."),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(verdict("You gave an answer too recently."), None);
+    }
+
+    #[test]
+    fn refuses_answers_outside_of_bounds() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(day!(1), 1, "101"),
+            Err(Rejection::NotBelow("100".into()))
+        );
+        assert_eq!(
+            answers.check(day!(1), 1, "20"),
+            Err(Rejection::KnownWrong(Verdict::TooLow))
+        );
+        assert_eq!(
+            answers.check(day!(1), 1, "-5"),
+            Err(Rejection::NotAbove("20".into()))
+        );
+        assert_eq!(answers.check(day!(1), 1, "42"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_for_solved_parts() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(day!(2), 2, "abc"),
+            Err(Rejection::AlreadyCorrect)
+        );
+        assert_eq!(
+            answers.check(day!(2), 2, "abd"),
+            Err(Rejection::DiffersFromCorrect("abc".into()))
+        );
+        assert_eq!(answers.check(day!(2), 1, "abd"), Ok(()));
+    }
+
+    #[test]
+    fn records_answers() {
+        let mut answers = get_mock_answers();
+        answers.record(Answer {
+            day: day!(1),
+            part: 1,
+            answer: "50".into(),
+            verdict: Verdict::TooLow,
+        });
+        assert_eq!(answers.data.len(), 4);
+        assert_eq!(answers.data[3].verdict, Verdict::TooLow);
+        assert_eq!(answers.correct_answer(day!(2), 2), Some("abc"));
+        assert_eq!(answers.correct_answer(day!(1), 1), None);
+    }
+
+    #[test]
+    fn warns_about_suspicious_answers() {
+        assert!(suspicious("0").is_some());
+        assert!(suspicious("-12").is_some());
+        assert!(suspicious("").is_some());
+        assert!(suspicious("12").is_none());
+        assert!(suspicious("ABC").is_none());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+        assert_eq!(parsed.data, answers.data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json =
+            r#"{ "data": [{ "day": "01", "part": 3, "answer": "1", "verdict": "correct" }] }"#;
+        Answers::try_from(json.to_string()).unwrap();
+    }
+}
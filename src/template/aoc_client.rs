@@ -0,0 +1,338 @@
+/// Built-in client for the Advent of Code website.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{answers::Verdict, markdown, PuzzleId};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!(
+    "github.com/fspoettel/advent-of-code-rust (advent_of_code ",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotSet,
+    Request(String),
+    BadStatus(u16),
+    PuzzleNotFound,
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create the file \"~/.adventofcode.session\"."
+            ),
+            AocClientError::YearNotSet => write!(f, "`AOC_YEAR` is not set to a valid year."),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus(status) => write!(
+                f,
+                "server responded with status {status}. Is your session cookie still valid?"
+            ),
+            AocClientError::PuzzleNotFound => {
+                write!(f, "could not find a puzzle description in the response.")
+            }
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// The server's reply to a submitted answer.
+pub struct Submission {
+    /// The verdict, if the reply carries one, see [`Verdict::from_response`].
+    pub verdict: Option<Verdict>,
+    pub message: String,
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Configures a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION`, the file at `AOC_SESSION_FILE` or `~/.adventofcode.session`.
+    ///  2. the base url defaults to adventofcode.com and can be overridden with `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::SessionNotFound)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetches the puzzle input for a day.
+    pub fn fetch_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        self.get(&format!("{}/input", puzzle_url(puzzle)?))
+    }
+
+    /// Fetches the puzzle description for a day and converts it to markdown.
+    pub fn fetch_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&puzzle_url(puzzle)?)?;
+        markdown::from_puzzle_page(&html).ok_or(AocClientError::PuzzleNotFound)
+    }
+
+    /// Downloads input and puzzle description for a day to the `data` folder.
+    pub fn download(&self, puzzle: PuzzleId) -> Result<(), AocClientError> {
+        let input_path = puzzle.data_path("inputs", "txt");
+        let puzzle_path = puzzle.data_path("puzzles", "md");
+
+        let input = self.fetch_input(puzzle)?;
+        let description = self.fetch_puzzle(puzzle)?;
+
+        write_file(&input_path, &input)?;
+        write_file(&puzzle_path, &description)?;
+
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            puzzle_path.display()
+        );
+        Ok(())
+    }
+
+    /// Downloads the puzzle description for a day and returns it as markdown.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let description = self.fetch_puzzle(puzzle)?;
+        write_file(&puzzle.data_path("puzzles", "md"), &description)?;
+        Ok(description)
+    }
+
+    /// Submits an answer for one part of a day.
+    pub fn submit(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<Submission, AocClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}{}/answer", self.base_url, puzzle_url(puzzle)?))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = read_response(response)?;
+        let message = markdown::from_puzzle_page(&html).ok_or(AocClientError::PuzzleNotFound)?;
+
+        Ok(Submission {
+            verdict: Verdict::from_response(&message),
+            message,
+        })
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_response(response)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Request(e.to_string())),
+        Err(ureq::Error::Status(status, _)) => Err(AocClientError::BadStatus(status)),
+        Err(e) => Err(AocClientError::Request(e.to_string())),
+    }
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session);
+        }
+    }
+
+    let path = match env::var("AOC_SESSION_FILE") {
+        Ok(path) => PathBuf::from(path),
+        Err(_) => {
+            let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+            Path::new(&home).join(".adventofcode.session")
+        }
+    };
+
+    fs::read_to_string(path)
+        .ok()
+        .filter(|session| !session.trim().is_empty())
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+fn puzzle_url(puzzle: PuzzleId) -> Result<String, AocClientError> {
+    let year = puzzle.resolve_year().ok_or(AocClientError::YearNotSet)?;
+    Ok(format!("/{year}/day/{}", puzzle.day().into_inner()))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::{AocClient, AocClientError};
+    use crate::{
+        day,
+        template::{answers::Verdict, Day, PuzzleId},
+        year,
+    };
+
+    fn get_puzzle(day: Day) -> PuzzleId {
+        PuzzleId::new(Some(year!(2022)), day)
+    }
+
+    /// Starts a stand-in server that answers `requests` requests and returns its base url.
+    /// Requests without the expected session cookie are rejected with a 400, like the real server does.
+    fn serve(requests: usize) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        thread::spawn(move || {
+            for stream in listener.incoming().take(requests) {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split(' ').nth(1).unwrap().to_string();
+
+                let mut authorized = false;
+                let mut content_length = 0;
+                loop {
+                    let mut header = String::new();
+                    reader.read_line(&mut header).unwrap();
+                    if header.trim().is_empty() {
+                        break;
+                    }
+                    authorized |= header.trim() == "Cookie: session=secret";
+                    if let Some(len) = header.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                }
+
+                let mut form = vec![0; content_length];
+                reader.read_exact(&mut form).unwrap();
+                let form = String::from_utf8(form).unwrap();
+
+                let (status, body) = match path.as_str() {
+                    _ if !authorized => ("400 Bad Request", "Puzzle inputs differ by user."),
+                    "/2022/day/1/input" => ("200 OK", "1abc2\npqr3stu8vwx\n"),
+                    "/2022/day/1" => (
+                        "200 OK",
+                        "<main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Hello <em>world</em></p></article></main>",
+                    ),
+                    "/2022/day/2" => ("200 OK", "<main>nothing here</main>"),
+                    "/2022/day/1/answer" => (
+                        "200 OK",
+                        match form.as_str() {
+                            "level=1&answer=142" => "<article><p>That's the right answer!</p></article>",
+                            "level=1&answer=200" => "<article><p>That's not the right answer; your answer is too high.</p></article>",
+                            "level=1&answer=1" => "<article><p>That's not the right answer; your answer is too low.</p></article>",
+                            "level=1&answer=ABC" => "<article><p>That's not the right answer.</p></article>",
+                            _ => "<article><p>You gave an answer too recently.</p></article>",
+                        },
+                    ),
+                    _ => ("404 Not Found", ""),
+                };
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+        });
+
+        format!("http://{addr}/")
+    }
+
+    #[test]
+    fn fetches_input() {
+        let client = AocClient::new(&serve(1), "secret\n");
+        assert_eq!(
+            client.fetch_input(get_puzzle(day!(1))).unwrap(),
+            "1abc2\npqr3stu8vwx\n"
+        );
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let client = AocClient::new(&serve(1), "secret");
+        assert_eq!(
+            client.fetch_puzzle(get_puzzle(day!(1))).unwrap(),
+            "## --- Day 1: Trebuchet?! ---\n\nHello *world*\n"
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_session() {
+        let client = AocClient::new(&serve(1), "expired");
+        assert!(matches!(
+            client.fetch_input(get_puzzle(day!(1))),
+            Err(AocClientError::BadStatus(400))
+        ));
+    }
+
+    #[test]
+    fn errors_for_pages_without_puzzle() {
+        let client = AocClient::new(&serve(1), "secret");
+        assert!(matches!(
+            client.fetch_puzzle(get_puzzle(day!(2))),
+            Err(AocClientError::PuzzleNotFound)
+        ));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let client = AocClient::new(&serve(5), "secret");
+        let verdict = |answer| {
+            client
+                .submit(get_puzzle(day!(1)), 1, answer)
+                .unwrap()
+                .verdict
+        };
+
+        assert_eq!(verdict("142"), Some(Verdict::Correct));
+        assert_eq!(verdict("200"), Some(Verdict::TooHigh));
+        assert_eq!(verdict("1"), Some(Verdict::TooLow));
+        assert_eq!(verdict("ABC"), Some(Verdict::Wrong));
+        assert_eq!(verdict("2"), None);
+    }
+}
@@ -0,0 +1,35 @@
+//! Helpers for parsing the command-line arguments of the main and the runner binary.
+use std::error::Error;
+
+use crate::template::{Day, PuzzleId, Year};
+
+/// Parses the free-standing `[year] [day]` arguments of a command.
+/// Needs to be called after all flags and options of the command have been parsed.
+pub fn parse_year_and_day(
+    args: &mut pico_args::Arguments,
+) -> Result<(Option<Year>, Option<Day>), Box<dyn Error>> {
+    let first: Option<String> = args.opt_free_from_str()?;
+    let second: Option<String> = args.opt_free_from_str()?;
+
+    match (first, second) {
+        (Some(year), Some(day)) => Ok((Some(year.parse()?), Some(day.parse()?))),
+        (Some(day), None) => Ok((None, Some(day.parse()?))),
+        _ => Ok((None, None)),
+    }
+}
+
+/// Parses the free-standing `[year] <day>` arguments of a command.
+pub fn parse_puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Box<dyn Error>> {
+    match parse_year_and_day(args)? {
+        (year, Some(day)) => Ok(PuzzleId::new(year, day)),
+        _ => Err(pico_args::Error::MissingArgument.into()),
+    }
+}
+
+/// Parses the `--iterations <samples>` option of the commands that bench days.
+pub fn parse_iterations(args: &mut pico_args::Arguments) -> Result<Option<u128>, Box<dyn Error>> {
+    match args.opt_value_from_str("--iterations")? {
+        Some(0) => Err("`--iterations` needs to be at least 1.".into()),
+        iterations => Ok(iterations),
+    }
+}
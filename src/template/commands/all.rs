@@ -1,5 +1,28 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days, expected::ExpectedAnswers, registry::Registry, run_multi::run_multi,
+    runner::RunOptions, Year,
+};
+
+pub fn handle(
+    registry: &Registry,
+    year: Option<Year>,
+    timeout: Option<Duration>,
+    jobs: Option<usize>,
+) {
+    let expected = ExpectedAnswers::read(year);
+    let summary = run_multi(
+        registry,
+        &all_days().collect(),
+        year,
+        &RunOptions::default(),
+        Some(&expected),
+        timeout,
+        jobs,
+    );
+
+    if summary.regressions > 0 || summary.failures > 0 {
+        process::exit(1);
+    }
 }
@@ -1,14 +1,11 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client::AocClient, PuzzleId};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+pub fn handle(puzzle: PuzzleId) {
+    let result = AocClient::from_env().and_then(|client| client.download(puzzle));
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = result {
+        eprintln!("failed to download {puzzle}: {e}");
         process::exit(1);
     };
 }
@@ -0,0 +1,93 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::{data_dir, examples, PuzzleId};
+
+/// Writes the examples of a downloaded puzzle description to `DD.txt` and `DD-2.txt`, and fills in
+/// the expected answers of the placeholder tests of the day's solution.
+pub fn handle(puzzle: PuzzleId) {
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Could not read \"{}\", download the puzzle description first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    for (part, example) in (1..).zip(examples::parse(&markdown)) {
+        if let Some(input) = &example.input {
+            write_example(&example_path(puzzle, part), input);
+        } else {
+            println!("No example found for part {part}.");
+        }
+
+        if example.other_blocks > 0 {
+            println!(
+                "Part {part} has {} more code block(s), see \"{}\".",
+                example.other_blocks,
+                puzzle_path.display()
+            );
+        }
+
+        if let Some(answer) = &example.answer {
+            fill_in_answer(puzzle, part, answer, example.input.is_some());
+        }
+    }
+}
+
+/// `DD.txt` for the first part, `DD-2.txt` for the second, see [`read_file_part`](crate::template::read_file_part).
+fn example_path(puzzle: PuzzleId, part: u8) -> PathBuf {
+    match part {
+        1 => puzzle.data_path("examples", "txt"),
+        _ => data_dir(puzzle.year())
+            .join("examples")
+            .join(format!("{}-{part}.txt", puzzle.day())),
+    }
+}
+
+/// Writes an example file, unless it already has content.
+fn write_example(path: &PathBuf, input: &str) {
+    let is_empty = fs::read_to_string(path).map_or(true, |s| s.trim().is_empty());
+
+    if !is_empty {
+        println!(
+            "Skipped example file \"{}\", it is not empty.",
+            path.display()
+        );
+        return;
+    }
+
+    let written = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, input));
+
+    match written {
+        Ok(()) => println!("Created example file \"{}\"", path.display()),
+        Err(e) => eprintln!("Failed to write example file \"{}\": {e}", path.display()),
+    }
+}
+
+/// Fills in the expected answer of a part in the day's tests, or suggests it if the test was already changed.
+fn fill_in_answer(puzzle: PuzzleId, part: u8, answer: &str, own_example: bool) {
+    let module_path = puzzle.bin_path();
+    let own_example = own_example && part > 1;
+
+    let filled = fs::read_to_string(&module_path)
+        .ok()
+        .and_then(|source| examples::fill_in_answer(&source, part, answer, own_example));
+
+    let Some(source) = filled else {
+        println!("Expected answer for part {part}: `{answer}`");
+        return;
+    };
+
+    match fs::write(&module_path, source) {
+        Ok(()) => println!(
+            "Filled in expected answer `{answer}` for part {part} in \"{}\"",
+            module_path.display()
+        ),
+        Err(e) => eprintln!("Failed to write \"{}\": {e}", module_path.display()),
+    }
+}
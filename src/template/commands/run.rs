@@ -0,0 +1,14 @@
+use std::process;
+
+use crate::template::{registry::Registry, runner::RunOptions, PuzzleId};
+
+/// Runs a single registered solution in this process.
+/// Used by `all` and `time` to run every day in a child process that can be killed once it times out.
+pub fn handle(registry: &Registry, puzzle: PuzzleId, options: &RunOptions) {
+    let Some(solution) = registry.get(puzzle) else {
+        eprintln!("Day {puzzle} is not solved.");
+        process::exit(1);
+    };
+
+    (solution.solve)(puzzle, options);
+}
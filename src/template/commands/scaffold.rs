@@ -1,73 +1,313 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, OpenOptions},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
     process,
 };
 
-use crate::template::Day;
+use crate::template::PuzzleId;
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
-}
+/// Project folder with user templates: `default.txt` replaces the built-in template, other files are
+/// variants that are selected with `--template <name>`.
+const TEMPLATES_DIR: &str = "templates";
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// What scaffolding did with one of the files of a day.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Created,
+    /// The file already existed and was left untouched.
+    Skipped,
+    /// The file existed but was empty, although it should have content.
+    Repaired,
+    /// The file was overwritten with `--force`, after copying it to a backup.
+    Replaced {
+        backup: PathBuf,
+    },
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
-
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+/// Creates a file with `contents` if it does not exist. Existing files are only written to if they are empty,
+/// or if `force` is set, in which case a backup is made first.
+fn scaffold_file(path: &Path, contents: &str, force: bool) -> Result<Outcome, std::io::Error> {
+    let existing = match fs::read(path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // fails instead of truncating if the file was created in the meantime.
+            let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+            file.write_all(contents.as_bytes())?;
+            return Ok(Outcome::Created);
         }
+        Err(e) => return Err(e),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    if existing == contents.as_bytes() {
+        return Ok(Outcome::Skipped);
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+    if existing.iter().all(u8::is_ascii_whitespace) && !contents.is_empty() {
+        fs::write(path, contents)?;
+        return Ok(Outcome::Repaired);
+    }
+
+    if !force {
+        return Ok(Outcome::Skipped);
+    }
+
+    let backup = backup_path(path);
+    fs::copy(path, &backup)?;
+    fs::write(path, contents)?;
+    Ok(Outcome::Replaced { backup })
+}
+
+/// The first of `<file>.bak`, `<file>.bak.1`, `<file>.bak.2`, ... that does not exist yet.
+fn backup_path(path: &Path) -> PathBuf {
+    let with_suffix = |suffix: String| {
+        let mut name = path.as_os_str().to_owned();
+        name.push(suffix);
+        PathBuf::from(name)
+    };
+
+    (0..)
+        .map(|i| match i {
+            0 => with_suffix(".bak".into()),
+            i => with_suffix(format!(".bak.{i}")),
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap()
+}
+
+/// Prints the outcome for a file, returns whether it succeeded.
+fn report(label: &str, path: &Path, outcome: Result<Outcome, std::io::Error>) -> bool {
+    let path = path.display();
+
+    match outcome {
+        Ok(Outcome::Created) => println!("Created {label} \"{path}\""),
+        Ok(Outcome::Skipped) => println!("Skipped {label} \"{path}\", it already exists."),
+        Ok(Outcome::Repaired) => println!("Repaired {label} \"{path}\", it was empty."),
+        Ok(Outcome::Replaced { backup }) => println!(
+            "Replaced {label} \"{path}\", the previous version was backed up to \"{}\"",
+            backup.display()
+        ),
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+            eprintln!("Failed to scaffold {label} \"{path}\": {e}");
+            return false;
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+    true
+}
+
+/// Reads the module template: the named variant, the project's `default.txt` or the built-in template.
+fn read_template(name: Option<&str>) -> Result<String, String> {
+    let path = |name: &str| PathBuf::from(TEMPLATES_DIR).join(format!("{name}.txt"));
+
+    match name {
+        Some(name) => fs::read_to_string(path(name)).map_err(|e| {
+            format!(
+                "Failed to read template \"{}\": {e}. Available templates: {}",
+                path(name).display(),
+                available_templates().join(", ")
+            )
+        }),
+        None => Ok(fs::read_to_string(path("default")).unwrap_or_else(|_| MODULE_TEMPLATE.into())),
+    }
+}
+
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// Substitutes the variables of a module template:
+///  - `%DAY_NUMBER%`: the day without padding, e.g. `5`.
+///  - `%DAY_PADDED%`: the day padded to two digits, e.g. `05`.
+///  - `%YEAR%`: the year of the puzzle, empty if none is configured.
+///  - `%PUZZLE_TITLE%`: the title of the puzzle if its description was downloaded, empty otherwise.
+fn render(template: &str, puzzle: PuzzleId, title: Option<&str>) -> String {
+    template
+        .replace("%DAY_NUMBER%", &puzzle.day().into_inner().to_string())
+        .replace("%DAY_PADDED%", &puzzle.day().to_string())
+        .replace(
+            "%YEAR%",
+            &puzzle
+                .resolve_year()
+                .map(|year| year.to_string())
+                .unwrap_or_default(),
+        )
+        .replace("%PUZZLE_TITLE%", title.unwrap_or_default())
+}
+
+/// The title of a downloaded puzzle description, e.g. `Trebuchet?!` for `## --- Day 1: Trebuchet?! ---`.
+fn puzzle_title(markdown: &str) -> Option<&str> {
+    let heading = markdown
+        .lines()
+        .find(|line| line.starts_with("## --- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim_end_matches('-').trim())
+}
+
+/// Creates the module, input and example files of a day. Existing files are left untouched unless `force` is set.
+pub fn handle(puzzle: PuzzleId, template: Option<&str>, force: bool) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.bin_path();
+
+    let template = match read_template(template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
+    };
+
+    let description = fs::read_to_string(puzzle.data_path("puzzles", "md")).ok();
+    let title = description.as_deref().and_then(puzzle_title);
+
+    let module = render(&template, puzzle, title);
+
+    // every file is scaffolded on its own, so a missing module can be restored without touching the input.
+    let results = [
+        report(
+            "module file",
+            &module_path,
+            scaffold_file(&module_path, &module, force),
+        ),
+        report(
+            "input file",
+            &input_path,
+            scaffold_file(&input_path, "", force),
+        ),
+        report(
+            "example file",
+            &example_path,
+            scaffold_file(&example_path, "", force),
+        ),
+    ];
+
+    if results.contains(&false) {
+        process::exit(1);
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    match puzzle.year() {
+        Some(year) => println!(
+            "🎄 Type `cargo solve {year} {}` to run your solution.",
+            puzzle.day()
+        ),
+        None => println!(
+            "🎄 Type `cargo solve {}` to run your solution.",
+            puzzle.day()
+        ),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{puzzle_title, render, scaffold_file, Outcome, MODULE_TEMPLATE};
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn renders_variables() {
+        let template = "// %YEAR% day %DAY_PADDED%: %PUZZLE_TITLE%\nsolution!(%DAY_NUMBER%);";
+        let puzzle = PuzzleId::new(Some(year!(2015)), day!(5));
+
+        assert_eq!(
+            render(
+                template,
+                puzzle,
+                Some("Doesn't He Have Intern-Elves For This?")
+            ),
+            "// 2015 day 05: Doesn't He Have Intern-Elves For This?\nsolution!(5);"
+        );
+        assert_eq!(
+            render(template, puzzle, None),
+            "// 2015 day 05: \nsolution!(5);"
+        );
+    }
+
+    #[test]
+    fn renders_builtin_template() {
+        let module = render(MODULE_TEMPLATE, PuzzleId::from(day!(12)), None);
+        assert!(module.starts_with("advent_of_code::solution!(12);"));
+        assert!(!module.contains('%'));
+    }
+
+    #[test]
+    fn extracts_puzzle_titles() {
+        let markdown = "## --- Day 1: Trebuchet?! ---\n\nSomething is wrong.\n";
+        assert_eq!(puzzle_title(markdown), Some("Trebuchet?!"));
+        assert_eq!(
+            puzzle_title("## --- Day 20: Pulse: Propagation ---"),
+            Some("Pulse: Propagation")
+        );
+        assert_eq!(puzzle_title("no description"), None);
+    }
+
+    #[test]
+    fn scaffolds_files_without_overwriting() {
+        let dir = env::temp_dir().join(format!("advent_of_code-scaffold-{}", process::id()));
+        let input = dir.join("inputs").join("01.txt");
+        let module = dir.join("01.rs");
+
+        // missing files and parent directories are created.
+        assert_eq!(scaffold_file(&input, "", false).unwrap(), Outcome::Created);
+        fs::write(&input, "downloaded").unwrap();
+        assert_eq!(scaffold_file(&input, "", false).unwrap(), Outcome::Skipped);
+        assert_eq!(fs::read_to_string(&input).unwrap(), "downloaded");
+
+        // a module that was left empty is restored from the template.
+        fs::write(&module, "\n").unwrap();
+        assert_eq!(
+            scaffold_file(&module, "fn main() {}", false).unwrap(),
+            Outcome::Repaired
+        );
+        assert_eq!(
+            scaffold_file(&module, "fn main() {}", true).unwrap(),
+            Outcome::Skipped
+        );
+
+        // forcing backs up the previous contents, without replacing older backups.
+        let first = scaffold_file(&input, "", true).unwrap();
+        assert_eq!(
+            first,
+            Outcome::Replaced {
+                backup: dir.join("inputs").join("01.txt.bak")
+            }
+        );
+        assert_eq!(fs::read_to_string(&input).unwrap(), "");
+        assert_eq!(
+            fs::read_to_string(dir.join("inputs").join("01.txt.bak")).unwrap(),
+            "downloaded"
+        );
+
+        fs::write(&input, "again").unwrap();
+        let second = scaffold_file(&input, "", true).unwrap();
+        assert_eq!(
+            second,
+            Outcome::Replaced {
+                backup: dir.join("inputs").join("01.txt.bak.1")
+            }
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -1,9 +1,12 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{
+    runner::{Format, RunOptions},
+    PuzzleId,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(puzzle: PuzzleId, release: bool, dhat: bool, options: &RunOptions) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -18,11 +21,31 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
 
     cmd_args.push("--".to_string());
 
-    if let Some(submit_part) = submit_part {
+    if let Some(submit_part) = options.submit {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if options.time {
+        cmd_args.push("--time".to_string());
+    }
+
+    if let Some(budget) = options.budget {
+        cmd_args.extend(["--budget".to_string(), budget.as_millis().to_string()]);
+    }
+
+    if let Some(iterations) = options.iterations {
+        cmd_args.extend(["--iterations".to_string(), iterations.to_string()]);
+    }
+
+    if options.format == Format::Json {
+        cmd_args.extend(["--format".to_string(), "json".to_string()]);
+    }
+
+    if let Some(records) = &options.records {
+        cmd_args.extend(["--records".to_string(), records.display().to_string()]);
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
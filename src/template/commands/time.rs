@@ -1,11 +1,26 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
+use crate::template::history::History;
+use crate::template::registry::Registry;
 use crate::template::run_multi::run_multi;
+use crate::template::runner::RunOptions;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    registry: &Registry,
+    year: Option<Year>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    options: RunOptions,
+    compare_threshold: Option<f64>,
+    timeout: Option<Duration>,
+) {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,20 +36,64 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        time: true,
+        ..options
+    };
+
+    let timings = run_multi(registry, &days_to_run, year, &options, None, timeout, None)
+        .timings
+        .unwrap();
+
+    let mut history = History::read_from_file(year);
+    let mut regressions = 0;
+
+    if let Some(threshold) = compare_threshold {
+        println!("\n{ANSI_BOLD}Compared to the previous run:{ANSI_RESET}");
+
+        let deltas = history.compare(&timings);
+        if deltas.is_empty() {
+            println!("No previous run to compare with.");
+        }
+
+        for delta in deltas {
+            if delta.is_regression(threshold) {
+                regressions += 1;
+                println!("{delta} ✖ slower than {threshold}%");
+            } else {
+                println!("{delta}");
+            }
+        }
+    }
+
+    history.record(&timings);
+    if let Err(e) = history.store_file(year) {
+        eprintln!("Failed to store benchmark history: {e}");
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+
+        // the readme only tracks the configured year, timings of other years are kept in their data folder.
+        if year.is_some() {
+            println!("Stored updated benchmarks.");
+        } else {
+            match readme_benchmarks::update(merged_timings) {
+                Ok(()) => {
+                    println!("Stored updated benchmarks.");
+                }
+                Err(_) => {
+                    eprintln!("Failed to store updated benchmarks.");
+                }
             }
         }
     }
+
+    // exit only after storing, so the history and the stored timings stay in sync.
+    if regressions > 0 {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,396 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    process::{Child, Command, Stdio},
+    thread::{self, JoinHandle},
+    time::{Duration, SystemTime},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    data_dir, run_multi::read_to_end, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Watches the source, examples and input of a day. On every change, the example tests are run,
+/// followed by the solution if they pass. A change during a run cancels it and starts over.
+pub fn handle(puzzle: PuzzleId, release: bool) {
+    let paths = watched_paths(puzzle);
+    let mut snapshot: Option<Vec<Option<SystemTime>>> = None;
+    let mut run: Option<Run> = None;
+
+    println!("Watching day {puzzle}, press Ctrl+C to stop.");
+
+    loop {
+        let current = modified_times(&paths);
+
+        if snapshot.as_ref() != Some(&current) {
+            if let Some(stale) = run.take() {
+                stale.cancel();
+                println!(
+                    "\n{ANSI_ITALIC}Change detected, cancelled the running checks.{ANSI_RESET}"
+                );
+            }
+            snapshot = Some(current);
+            run = Run::start(puzzle, release);
+        }
+
+        if run.as_mut().is_some_and(Run::poll) {
+            run = None;
+            println!("\n{ANSI_ITALIC}Waiting for changes...{ANSI_RESET}");
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn watched_paths(puzzle: PuzzleId) -> Vec<PathBuf> {
+    vec![
+        puzzle.bin_path(),
+        puzzle.data_path("examples", "txt"),
+        data_dir(puzzle.year())
+            .join("examples")
+            .join(format!("{}-2.txt", puzzle.day())),
+        puzzle.data_path("inputs", "txt"),
+    ]
+}
+
+/// The modification times of the watched files. Files that do not exist (yet) are [`None`].
+fn modified_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A run of the checks for a day, which builds and runs the example tests and then the solution.
+///
+/// The test and solution binaries are started by the watcher itself instead of `cargo test` or `cargo run`,
+/// so cancelling a run kills them directly. Killing cargo would leave the binaries it started running.
+struct Run {
+    puzzle: PuzzleId,
+    release: bool,
+    stage: Stage,
+}
+
+/// What a [`Stage::Build`] builds.
+#[derive(Clone, Copy)]
+enum Target {
+    Tests,
+    Solution,
+}
+
+impl Target {
+    fn label(self) -> &'static str {
+        match self {
+            Target::Tests => "Tests",
+            Target::Solution => "Solution",
+        }
+    }
+}
+
+enum Stage {
+    /// `cargo test --no-run` or `cargo build` with their output captured, stdout holds the built artifacts.
+    Build {
+        target: Target,
+        child: Child,
+        stdout: Option<JoinHandle<Vec<u8>>>,
+        stderr: Option<JoinHandle<Vec<u8>>>,
+    },
+    /// The test binary with its output captured, it is summarized once the tests finish.
+    Test {
+        child: Child,
+        stdout: Option<JoinHandle<Vec<u8>>>,
+    },
+    /// The solution binary with its output passed through to the console.
+    Solve { child: Child },
+}
+
+impl Run {
+    fn start(puzzle: PuzzleId, release: bool) -> Option<Self> {
+        println!("\n{ANSI_BOLD}Day {puzzle}{ANSI_RESET}");
+        println!("------");
+
+        let stage = build(Target::Tests, puzzle, release)?;
+        Some(Self {
+            puzzle,
+            release,
+            stage,
+        })
+    }
+
+    /// Advances the run to its next stage once the current one finishes. Returns `true` when the run is done.
+    fn poll(&mut self) -> bool {
+        if matches!(self.stage.child().try_wait(), Ok(None)) {
+            return false;
+        }
+
+        let next = match &mut self.stage {
+            Stage::Solve { .. } => return true,
+            Stage::Build {
+                target,
+                stdout,
+                stderr,
+                ..
+            } => {
+                let Some(executable) = built_executable(&join(stdout)) else {
+                    // the day does not compile, cargo renders the errors to stderr.
+                    println!("{}: ✖ {ANSI_BOLD}build failed{ANSI_RESET}", target.label());
+                    eprint!("{}", join(stderr));
+                    return true;
+                };
+
+                match target {
+                    Target::Tests => spawn_tests(&executable),
+                    Target::Solution => Command::new(executable)
+                        .spawn()
+                        .map(|child| Stage::Solve { child })
+                        .map_err(|e| eprintln!("Failed to run solution: {e}"))
+                        .ok(),
+                }
+            }
+            Stage::Test { stdout, .. } => {
+                if !print_test_summary(&join(stdout)) {
+                    return true;
+                }
+                build(Target::Solution, self.puzzle, self.release)
+            }
+        };
+
+        match next {
+            Some(stage) => {
+                self.stage = stage;
+                false
+            }
+            None => true,
+        }
+    }
+
+    fn cancel(mut self) {
+        let child = self.stage.child();
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
+
+impl Stage {
+    fn child(&mut self) -> &mut Child {
+        match self {
+            Stage::Build { child, .. } | Stage::Test { child, .. } | Stage::Solve { child } => {
+                child
+            }
+        }
+    }
+}
+
+/// Starts building the tests or the solution of a day.
+fn build(target: Target, puzzle: PuzzleId, release: bool) -> Option<Stage> {
+    let mut cmd = Command::new("cargo");
+    match target {
+        Target::Tests => cmd.args(["test", "--no-run"]),
+        Target::Solution => cmd.arg("build"),
+    };
+    cmd.args([
+        "--quiet",
+        "--message-format=json-render-diagnostics",
+        "--bin",
+        &puzzle.to_string(),
+    ]);
+    if release {
+        cmd.arg("--release");
+    }
+
+    match cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn() {
+        Ok(mut child) => Some(Stage::Build {
+            target,
+            stdout: child.stdout.take().map(read_to_end),
+            stderr: child.stderr.take().map(read_to_end),
+            child,
+        }),
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            None
+        }
+    }
+}
+
+fn spawn_tests(executable: &str) -> Option<Stage> {
+    // backtraces of failing assertions do not fit into a compact summary.
+    let spawned = Command::new(executable)
+        .arg("--quiet")
+        .env("RUST_BACKTRACE", "0")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+
+    match spawned {
+        Ok(mut child) => Some(Stage::Test {
+            stdout: child.stdout.take().map(read_to_end),
+            child,
+        }),
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            None
+        }
+    }
+}
+
+/// The executable of the last artifact in the JSON messages of a cargo build, [`None`] if nothing was built.
+fn built_executable(messages: &str) -> Option<String> {
+    messages.lines().rev().find_map(|line| {
+        let message = line.parse::<JsonValue>().ok()?;
+        let fields = message.get::<HashMap<String, JsonValue>>()?;
+        fields.get("executable")?.get::<String>().cloned()
+    })
+}
+
+/// Prints a compact summary of a test run, with the details of failing tests.
+/// Returns whether all tests passed.
+fn print_test_summary(stdout: &str) -> bool {
+    let Some(summary) = TestSummary::parse(stdout) else {
+        println!("Tests: ✖ {ANSI_BOLD}did not finish{ANSI_RESET}");
+        return false;
+    };
+
+    if summary.failed == 0 {
+        println!("Tests: ✔ {} passed", summary.passed);
+        return true;
+    }
+
+    println!(
+        "Tests: ✖ {ANSI_BOLD}{} failed{ANSI_RESET}, {} passed",
+        summary.failed, summary.passed
+    );
+    if let Some(details) = failure_details(stdout) {
+        println!("{details}");
+    }
+    false
+}
+
+/// The number of passed and failed tests of a `cargo test` run.
+#[derive(Debug, PartialEq, Eq)]
+struct TestSummary {
+    passed: usize,
+    failed: usize,
+}
+
+impl TestSummary {
+    /// Sums up the `test result: ...` lines of the output, [`None`] if there are none.
+    fn parse(output: &str) -> Option<Self> {
+        output
+            .lines()
+            .filter_map(|line| line.strip_prefix("test result: "))
+            .map(|result| {
+                let count = |label: &str| {
+                    result
+                        .split([';', '.'])
+                        .find_map(|s| s.trim().strip_suffix(label)?.trim().parse().ok())
+                        .unwrap_or(0)
+                };
+                Self {
+                    passed: count("passed"),
+                    failed: count("failed"),
+                }
+            })
+            .reduce(|a, b| Self {
+                passed: a.passed + b.passed,
+                failed: a.failed + b.failed,
+            })
+    }
+}
+
+/// The output of the failing tests, which the test binary prints between two `failures:` lines.
+fn failure_details(output: &str) -> Option<&str> {
+    let (_, rest) = output.split_once("\nfailures:\n")?;
+    let details = rest.split_once("\nfailures:\n").map_or(rest, |(d, _)| d);
+    Some(details.trim())
+}
+
+fn join(reader: &mut Option<JoinHandle<Vec<u8>>>) -> String {
+    let output = reader
+        .take()
+        .and_then(|reader| reader.join().ok())
+        .unwrap_or_default();
+    String::from_utf8_lossy(&output).into_owned()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{built_executable, failure_details, TestSummary};
+
+    const PASSED: &str = "
+running 2 tests
+..
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+";
+
+    const FAILED: &str = "
+running 2 tests
+.F
+failures:
+
+---- tests::test_part_two stdout ----
+
+thread 'tests::test_part_two' panicked at src/bin/01.rs:27:9:
+assertion `left == right` failed
+  left: Some(3)
+ right: None
+
+failures:
+    tests::test_part_two
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+
+";
+
+    #[test]
+    fn summarizes_test_runs() {
+        assert_eq!(
+            TestSummary::parse(PASSED),
+            Some(TestSummary {
+                passed: 2,
+                failed: 0
+            })
+        );
+        assert_eq!(
+            TestSummary::parse(FAILED),
+            Some(TestSummary {
+                passed: 1,
+                failed: 1
+            })
+        );
+        assert_eq!(TestSummary::parse("error[E0425]: cannot find value"), None);
+    }
+
+    #[test]
+    fn extracts_failure_details() {
+        let details = failure_details(FAILED).unwrap();
+        assert!(details.starts_with("---- tests::test_part_two stdout ----"));
+        assert!(details.ends_with(" right: None"));
+        assert_eq!(failure_details(PASSED), None);
+    }
+
+    #[test]
+    fn finds_built_executables() {
+        let messages = r#"{"reason":"compiler-artifact","target":{"name":"advent_of_code"},"executable":null}
+{"reason":"compiler-artifact","target":{"name":"09"},"executable":"/repo/target/debug/deps/09-cb27849200fc157b"}
+{"reason":"build-finished","success":true}
+"#;
+        assert_eq!(
+            built_executable(messages).as_deref(),
+            Some("/repo/target/debug/deps/09-cb27849200fc157b")
+        );
+        assert_eq!(
+            built_executable(r#"{"reason":"build-finished","success":false}"#),
+            None
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Extracts example inputs and their answers from the markdown of a downloaded puzzle description.
+
+/// The example of one part of a puzzle.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PartExample {
+    /// The first code block of the part, which holds the example input in nearly all puzzles.
+    pub input: Option<String>,
+    /// The last emphasized code of the part, which usually is the answer for the example.
+    pub answer: Option<String>,
+    /// The number of code blocks after the input, e.g. intermediate states or further examples.
+    pub other_blocks: usize,
+}
+
+/// Splits a puzzle description into its parts and extracts their examples.
+pub fn parse(markdown: &str) -> Vec<PartExample> {
+    markdown
+        .split("## --- Part Two ---")
+        .map(|section| {
+            let mut blocks = code_blocks(section);
+            let input = (!blocks.is_empty()).then(|| blocks.remove(0));
+
+            PartExample {
+                input,
+                answer: emphasized_code(section).last().map(ToString::to_string),
+                other_blocks: blocks.len(),
+            }
+        })
+        .collect()
+}
+
+/// The contents of all fenced code blocks, with a trailing line break.
+fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut current: Option<String> = None;
+
+    for line in markdown.lines() {
+        match (&mut current, line.trim_end() == "```") {
+            (None, true) => current = Some(String::new()),
+            (Some(_), true) => blocks.extend(current.take()),
+            (Some(block), false) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            (None, false) => {}
+        }
+    }
+
+    blocks
+}
+
+/// All inline code that is emphasized, written as ``*`code`*`` by the markdown conversion.
+fn emphasized_code(markdown: &str) -> Vec<&str> {
+    let mut found = vec![];
+    let mut rest = markdown;
+
+    while let Some(start) = rest.find("*`") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("`*") else {
+            break;
+        };
+        found.push(&rest[..end]);
+        rest = &rest[end + 2..];
+    }
+
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Fills in the expected answer of a part in the placeholder test of a scaffolded solution,
+/// `assert_eq!(result, None);` in `test_part_one` or `test_part_two`.
+/// Returns the updated source, or [`None`] if the test does not have a placeholder anymore or the answer is
+/// not a number, which would not match the `Option<u32>` of the template.
+///
+/// Part two reads its own example from `DD-2.txt` if `own_example` is set.
+pub fn fill_in_answer(source: &str, part: u8, answer: &str, own_example: bool) -> Option<String> {
+    const PLACEHOLDER: &str = "assert_eq!(result, None);";
+
+    if answer.parse::<i128>().is_err() {
+        return None;
+    }
+
+    let name = match part {
+        1 => "fn test_part_one()",
+        2 => "fn test_part_two()",
+        _ => return None,
+    };
+
+    let start = source.find(name)?;
+    // the test ends with the first line that closes a block at the indentation of the function.
+    let end = start + source[start..].find("\n    }")?;
+    let test = &source[start..end];
+
+    if !test.contains(PLACEHOLDER) {
+        return None;
+    }
+
+    let mut filled = test.replace(PLACEHOLDER, &format!("assert_eq!(result, Some({answer}));"));
+    if part == 2 && own_example {
+        filled = filled.replace(
+            "read_file(\"examples\", PUZZLE)",
+            "read_file_part(\"examples\", PUZZLE, 2)",
+        );
+    }
+
+    Some(format!("{}{filled}{}", &source[..start], &source[end..]))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fill_in_answer, parse, PartExample};
+
+    const TEMPLATE: &str = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+The newly-improved calibration document consists of lines of text, e.g. `1abc2`.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values are `12` and *`38`*. Adding these together produces *`50`*.
+
+```
+12 + 38
+```
+
+## --- Part Two ---
+
+It looks like some of the digits are actually *spelled out*.
+
+```
+two1nine
+```
+
+Adding these together produces *`29`*.
+";
+
+    #[test]
+    fn extracts_examples_per_part() {
+        assert_eq!(
+            parse(PUZZLE),
+            [
+                PartExample {
+                    input: Some("1abc2\npqr3stu8vwx\n".into()),
+                    answer: Some("50".into()),
+                    other_blocks: 1,
+                },
+                PartExample {
+                    input: Some("two1nine\n".into()),
+                    answer: Some("29".into()),
+                    other_blocks: 0,
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_parts_without_examples() {
+        let examples = parse("## --- Day 2 ---\n\nNo examples here, *sorry*.\n");
+        assert_eq!(examples, [PartExample::default()]);
+    }
+
+    #[test]
+    fn fills_in_placeholder_tests() {
+        let source = fill_in_answer(TEMPLATE, 1, "50", false).unwrap();
+        assert!(source.contains("assert_eq!(result, Some(50));"));
+        assert_eq!(source.matches("assert_eq!(result, None);").count(), 1);
+
+        assert_eq!(fill_in_answer(&source, 2, "ABC", true), None);
+        let source = fill_in_answer(&source, 2, "281", true).unwrap();
+        assert!(source.contains("assert_eq!(result, Some(281));"));
+        assert!(source.contains("read_file_part(\"examples\", PUZZLE, 2)"));
+        assert_eq!(source.matches("read_file(\"examples\", PUZZLE)").count(), 1);
+
+        // tests that were already filled in are left alone.
+        assert_eq!(fill_in_answer(&source, 1, "51", false), None);
+    }
+}
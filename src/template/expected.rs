@@ -0,0 +1,227 @@
+/// Expected answers per day and part, used to detect regressions when running all solutions.
+use std::{collections::HashMap, fmt::Display, fs, path::PathBuf, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{answers::Answers, data_dir, Day, Year, ANSI_BOLD, ANSI_RESET};
+
+static EXPECTED_FILE_NAME: &str = "expected.json";
+
+/// Outcome of comparing an answer with its expected value.
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    /// The answer matches the expected answer.
+    Pass,
+    /// The part did not produce an answer although one is expected.
+    Fail { expected: String },
+    /// The answer differs from the expected answer.
+    Changed { expected: String, actual: String },
+    /// There is no expected answer for this part.
+    Unchecked,
+}
+
+impl Status {
+    pub fn is_regression(&self) -> bool {
+        matches!(self, Status::Fail { .. } | Status::Changed { .. })
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "✔ pass"),
+            Status::Fail { expected } => {
+                write!(f, "✖ {ANSI_BOLD}fail{ANSI_RESET} (expected `{expected}`)")
+            }
+            Status::Changed { expected, actual } => write!(
+                f,
+                "✖ {ANSI_BOLD}changed{ANSI_RESET} (expected `{expected}`, got `{actual}`)"
+            ),
+            Status::Unchecked => write!(f, "- no expected answer"),
+        }
+    }
+}
+
+/// Expected answers for a set of days.
+/// Answers are read from `expected.json` and complemented by the accepted answers of the answer ledger.
+#[derive(Clone, Debug, Default)]
+pub struct ExpectedAnswers {
+    data: HashMap<(Day, u8), String>,
+}
+
+impl ExpectedAnswers {
+    /// Reads the expected answers of an event.
+    pub fn read(year: Option<Year>) -> Self {
+        let mut expected = Self::from_answers(&Answers::read_from_file(year));
+
+        if let Ok(s) = fs::read_to_string(get_file_path(year)) {
+            match ExpectedAnswers::try_from(s) {
+                Ok(file) => expected.data.extend(file.data),
+                Err(e) => eprintln!("{e}"),
+            }
+        }
+
+        expected
+    }
+
+    fn from_answers(answers: &Answers) -> Self {
+        let data = answers
+            .data
+            .iter()
+            .filter_map(|a| {
+                answers
+                    .correct_answer(a.day, a.part)
+                    .map(|correct| ((a.day, a.part), correct.to_string()))
+            })
+            .collect();
+
+        Self { data }
+    }
+
+    /// Returns the expected answer for a part if it is known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        self.data.get(&(day, part)).map(String::as_str)
+    }
+
+    /// Compare an answer with its expected value.
+    pub fn check(&self, day: Day, part: u8, answer: Option<&str>) -> Status {
+        match (self.get(day, part), answer) {
+            (None, _) => Status::Unchecked,
+            (Some(expected), None) => Status::Fail {
+                expected: expected.into(),
+            },
+            (Some(expected), Some(actual)) if expected == actual.trim() => Status::Pass,
+            (Some(expected), Some(actual)) => Status::Changed {
+                expected: expected.into(),
+                actual: actual.into(),
+            },
+        }
+    }
+}
+
+fn get_file_path(year: Option<Year>) -> PathBuf {
+    data_dir(year).join(EXPECTED_FILE_NAME)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for ExpectedAnswers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        let mut data = HashMap::new();
+
+        for value in json_data {
+            let json = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected answer to be a JSON object.")?;
+
+            let day = json
+                .get("day")
+                .and_then(|v| v.get::<String>())
+                .and_then(|day| Day::from_str(day).ok())
+                .ok_or("Expected answer.day to be a Day struct.")?;
+
+            for (part, key) in [(1, "part_1"), (2, "part_2")] {
+                match json.get(key) {
+                    None | Some(JsonValue::Null) => {}
+                    Some(JsonValue::String(answer)) => {
+                        data.insert((day, part), answer.clone());
+                    }
+                    Some(_) => return Err(format!("Expected answer.{key} to be null or string.")),
+                }
+            }
+        }
+
+        Ok(ExpectedAnswers { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ExpectedAnswers, Status};
+    use crate::{
+        day,
+        template::answers::{Answer, Answers, Verdict},
+    };
+
+    fn get_mock_expected() -> ExpectedAnswers {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "142", "part_2": null }, { "day": "02", "part_2": "abc" }] }"#;
+        ExpectedAnswers::try_from(json.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parses_json() {
+        let expected = get_mock_expected();
+        assert_eq!(expected.get(day!(1), 1), Some("142"));
+        assert_eq!(expected.get(day!(1), 2), None);
+        assert_eq!(expected.get(day!(2), 2), Some("abc"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_json() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": 142 }] }"#;
+        ExpectedAnswers::try_from(json.to_string()).unwrap();
+    }
+
+    #[test]
+    fn uses_accepted_answers_of_ledger() {
+        let answers = Answers {
+            data: vec![
+                Answer {
+                    day: day!(3),
+                    part: 1,
+                    answer: "10".into(),
+                    verdict: Verdict::TooLow,
+                },
+                Answer {
+                    day: day!(3),
+                    part: 1,
+                    answer: "12".into(),
+                    verdict: Verdict::Correct,
+                },
+            ],
+        };
+
+        let expected = ExpectedAnswers::from_answers(&answers);
+        assert_eq!(expected.get(day!(3), 1), Some("12"));
+        assert_eq!(expected.get(day!(3), 2), None);
+    }
+
+    #[test]
+    fn checks_answers() {
+        let expected = get_mock_expected();
+        assert_eq!(expected.check(day!(1), 1, Some("142")), Status::Pass);
+        assert_eq!(
+            expected.check(day!(1), 1, Some("143")),
+            Status::Changed {
+                expected: "142".into(),
+                actual: "143".into()
+            }
+        );
+        assert_eq!(
+            expected.check(day!(1), 1, None),
+            Status::Fail {
+                expected: "142".into()
+            }
+        );
+        assert_eq!(expected.check(day!(1), 2, Some("1")), Status::Unchecked);
+        assert!(!Status::Unchecked.is_regression());
+        assert!(Status::Fail {
+            expected: "1".into()
+        }
+        .is_regression());
+    }
+}
@@ -0,0 +1,382 @@
+/// History of benchmark results, used to detect performance regressions between runs.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    path::PathBuf,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    data_dir, runner::PARSE, timings::Timings, Day, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+/// The median time of a part, or the parse step, in one run of `cargo time`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The git commit that was checked out, if any.
+    pub commit: Option<String>,
+    pub day: Day,
+    pub part: u8,
+    pub nanos: f64,
+}
+
+/// Every benchmark result of an event, oldest first.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub data: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Dehydrate the history to the JSON file of an event.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let path = get_file_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history from the JSON file of an event. If not present, returns an empty history.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let Ok(s) = fs::read_to_string(get_file_path(year)) else {
+            return History::default();
+        };
+
+        match History::try_from(s) {
+            Ok(history) => history,
+            Err(e) => {
+                eprintln!("{e}");
+                History::default()
+            }
+        }
+    }
+
+    /// Append the benched parts of a run, tagged with the current time and git commit.
+    pub fn record(&mut self, timings: &Timings) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        self.record_at(timings, timestamp, &current_commit());
+    }
+
+    fn record_at(&mut self, timings: &Timings, timestamp: u64, commit: &Option<String>) {
+        for timing in &timings.data {
+            for (part, stats) in [
+                (PARSE, timing.parse_stats),
+                (1, timing.part_1_stats),
+                (2, timing.part_2_stats),
+            ] {
+                let Some(stats) = stats else {
+                    continue;
+                };
+
+                #[allow(clippy::cast_precision_loss)]
+                self.data.push(HistoryEntry {
+                    timestamp,
+                    commit: commit.clone(),
+                    day: timing.day,
+                    part,
+                    nanos: stats.median.as_nanos() as f64,
+                });
+            }
+        }
+    }
+
+    /// Returns the most recent entry of a part.
+    pub fn latest(&self, day: Day, part: u8) -> Option<&HistoryEntry> {
+        self.data
+            .iter()
+            .rev()
+            .find(|entry| entry.day == day && entry.part == part)
+    }
+
+    /// Compare the benched parts of a run with their most recent entry.
+    pub fn compare(&self, timings: &Timings) -> Vec<Delta> {
+        timings
+            .data
+            .iter()
+            .flat_map(|timing| {
+                [
+                    (PARSE, timing.parse_stats),
+                    (1, timing.part_1_stats),
+                    (2, timing.part_2_stats),
+                ]
+                .into_iter()
+                .filter_map(move |(part, stats)| Some((timing.day, part, stats?)))
+            })
+            .filter_map(|(day, part, stats)| {
+                let previous = self.latest(day, part)?;
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Some(Delta {
+                    day,
+                    part,
+                    previous: Duration::from_nanos(previous.nanos as u64),
+                    current: stats.median,
+                })
+            })
+            .collect()
+    }
+}
+
+fn get_file_path(year: Option<Year>) -> PathBuf {
+    data_dir(year).join(HISTORY_FILE_NAME)
+}
+
+/// Returns the short hash of the checked out git commit.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (output.status.success() && !commit.is_empty()).then_some(commit)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Change in the median time of a part between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    pub part: u8,
+    pub previous: Duration,
+    pub current: Duration,
+}
+
+impl Delta {
+    /// Relative change in percent, positive if the part got slower.
+    pub fn percent(&self) -> f64 {
+        let previous = self.previous.as_secs_f64();
+        if previous == 0.0 {
+            return 0.0;
+        }
+        (self.current.as_secs_f64() - previous) / previous * 100.0
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+impl Display for Delta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let part = match self.part {
+            PARSE => "Parse".to_string(),
+            part => format!("Part {part}"),
+        };
+
+        write!(
+            f,
+            "Day {} {part}: {:.1?} → {ANSI_BOLD}{:.1?}{ANSI_RESET} ({:+.1}%)",
+            self.day,
+            self.previous,
+            self.current,
+            self.percent()
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<History> for JsonValue {
+    fn from(value: History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(History {
+            data: json_data
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = match json.get("commit") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(commit)) => Some(commit.clone()),
+            Some(_) => return Err("Expected entry.commit to be null or string.".into()),
+        };
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| [0.0, 1.0, 2.0].contains(*part))
+            .ok_or("Expected entry.part to be 0 (parse), 1 or 2.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected entry.nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(HistoryEntry {
+            timestamp: *timestamp as u64,
+            commit,
+            day,
+            part: *part as u8,
+            nanos: *nanos,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Delta, History};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{Timing, Timings},
+            Day,
+        },
+    };
+    use std::time::Duration;
+
+    fn get_mock_timings(day: Day, nanos: u64) -> Timings {
+        let stats = Stats::from_samples(&[Duration::from_nanos(nanos)]);
+
+        Timings {
+            data: vec![Timing {
+                day,
+                part_1: Some(format!("{nanos}ns")),
+                part_2: None,
+                part_1_stats: stats,
+                total_nanos: 0_f64,
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn records_benched_parts() {
+        let mut history = History::default();
+        history.record_at(&get_mock_timings(day!(1), 100), 10, &Some("abc123".into()));
+        history.record_at(&get_mock_timings(day!(1), 200), 20, &None);
+
+        assert_eq!(history.data.len(), 2);
+        assert_eq!(history.latest(day!(1), 1).unwrap().timestamp, 20);
+        assert_eq!(history.data[0].commit.as_deref(), Some("abc123"));
+        assert!(history.latest(day!(1), 2).is_none());
+    }
+
+    #[test]
+    fn compares_with_latest_run() {
+        let mut history = History::default();
+        history.record_at(&get_mock_timings(day!(1), 100), 10, &None);
+        history.record_at(&get_mock_timings(day!(1), 200), 20, &None);
+
+        let deltas = history.compare(&get_mock_timings(day!(1), 250));
+        assert_eq!(
+            deltas,
+            vec![Delta {
+                day: day!(1),
+                part: 1,
+                previous: Duration::from_nanos(200),
+                current: Duration::from_nanos(250),
+            }]
+        );
+        assert!((deltas[0].percent() - 25.0).abs() < 1e-9);
+        assert!(deltas[0].is_regression(10.0));
+        assert!(!deltas[0].is_regression(30.0));
+
+        assert!(history.compare(&get_mock_timings(day!(2), 250)).is_empty());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut history = History::default();
+        history.record_at(&get_mock_timings(day!(3), 100), 10, &Some("abc123".into()));
+        history.record_at(&get_mock_timings(day!(3), 200), 20, &None);
+
+        let json = tinyjson::JsonValue::from(history.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(History::try_from(json).unwrap().data, history.data);
+    }
+}
@@ -0,0 +1,209 @@
+//! Converts the puzzle pages served by adventofcode.com to markdown.
+//! Only the subset of HTML used in puzzle descriptions is supported.
+
+/// Extracts all `<article>` elements from a puzzle page and converts them to markdown.
+/// Returns [`None`] if the page does not contain a puzzle description.
+pub fn from_puzzle_page(html: &str) -> Option<String> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let body = &rest[start..];
+        let body_start = body.find('>')? + 1;
+        let body_end = body.find("</article>")?;
+        articles.push(from_html(&body[body_start..body_end]));
+        rest = &body[body_end..];
+    }
+
+    if articles.is_empty() {
+        None
+    } else {
+        Some(articles.join("\n"))
+    }
+}
+
+/// Converts an HTML fragment to markdown.
+pub fn from_html(html: &str) -> String {
+    let mut out = String::new();
+    // buffered inline `<code>` content and whether it contained an `<em>`.
+    let mut code: Option<(String, bool)> = None;
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, &mut code, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &mut code, &rest[..tag_start], in_pre);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            push_text(&mut out, &mut code, &rest[tag_start..], in_pre);
+            break;
+        };
+
+        let tag = Tag::parse(&rest[tag_start + 1..tag_start + tag_len]);
+        rest = &rest[tag_start + tag_len + 1..];
+
+        match (tag.name.as_str(), tag.closing) {
+            ("pre", false) => {
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+                in_pre = false;
+            }
+            // formatting inside of code blocks is dropped.
+            _ if in_pre => {}
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p", true) => out.push_str("\n\n"),
+            ("ul", true) => out.push('\n'),
+            ("li", false) => out.push_str("- "),
+            ("li", true) => out.push('\n'),
+            ("br", _) => out.push('\n'),
+            ("code", false) => code = Some((String::new(), false)),
+            ("code", true) => {
+                if let Some((content, emphasized)) = code.take() {
+                    if emphasized {
+                        out.push_str(&format!("*`{content}`*"));
+                    } else {
+                        out.push_str(&format!("`{content}`"));
+                    }
+                }
+            }
+            ("em", false) => match code.as_mut() {
+                Some((_, emphasized)) => *emphasized = true,
+                None => out.push('*'),
+            },
+            ("em", true) if code.is_none() => out.push('*'),
+            ("a", false) => {
+                links.push(tag.href.unwrap_or_default());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({href})"));
+            }
+            _ => {}
+        }
+    }
+
+    let mut out = out.trim_end().to_string();
+    out.push('\n');
+    out
+}
+
+fn push_text(out: &mut String, code: &mut Option<(String, bool)>, text: &str, in_pre: bool) {
+    // line breaks between block elements are already covered by the block's markdown.
+    if !in_pre && out.ends_with('\n') && text.trim().is_empty() {
+        return;
+    }
+
+    let text = decode_entities(text);
+    match code {
+        Some((content, _)) => content.push_str(&text),
+        None => out.push_str(&text),
+    }
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+struct Tag {
+    name: String,
+    closing: bool,
+    href: Option<String>,
+}
+
+impl Tag {
+    fn parse(s: &str) -> Self {
+        let s = s.trim().trim_end_matches('/');
+        let (closing, s) = match s.strip_prefix('/') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+
+        let name = s
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+
+        let href = s.split("href=\"").nth(1).and_then(|rest| {
+            rest.split('"')
+                .next()
+                .map(|href| decode_entities(href).to_string())
+        });
+
+        Self {
+            name,
+            closing,
+            href,
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{from_html, from_puzzle_page};
+
+    #[test]
+    fn converts_puzzle_markup() {
+        let html = r#"<h2>--- Day 1: Trebuchet?! ---</h2><p>Read <a href="/2023/about">this</a> &amp; <em>that</em>.</p>
+<pre><code>1abc2
+<em>pqr3stu8vwx</em>
+</code></pre>
+<ul><li>one</li><li>two</li></ul>
+<p>Adding these together produces <code><em>142</em></code>, not <code>&lt;3</code>.</p>"#;
+
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "Read [this](/2023/about) & *that*.",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "```",
+            "",
+            "- one",
+            "- two",
+            "",
+            "Adding these together produces *`142`*, not `<3`.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(from_html(html), expected);
+    }
+
+    #[test]
+    fn extracts_all_articles() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 2 ---</h2></article>
+<p>Your puzzle answer was <code>8</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2></article></main>"#;
+
+        assert_eq!(
+            from_puzzle_page(html).unwrap(),
+            "## --- Day 2 ---\n\n## --- Part Two ---\n"
+        );
+    }
+
+    #[test]
+    fn handles_pages_without_articles() {
+        assert_eq!(from_puzzle_page("<main>404</main>"), None);
+    }
+}
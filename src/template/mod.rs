@@ -1,15 +1,31 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
+pub mod args;
 pub mod commands;
+pub mod registry;
 pub mod runner;
+pub mod stats;
 
 pub use day::*;
+pub use puzzle::*;
+pub use year::*;
 
 mod day;
+mod examples;
+mod expected;
+mod history;
+mod markdown;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod year;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -17,28 +33,37 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: impl Into<PuzzleId>) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.into().data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<PuzzleId>, part: u8) -> String {
+    let puzzle = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(puzzle.year()))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day()));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The puzzle's year is derived from the binary name, see [`PuzzleId::from_bin_name`].
+///
+/// Solutions can declare a parse step with `solution!(10, parse = parse)`. The parser turns the input into
+/// a value that is passed to both parts by reference, and is timed as its own step. If it panics, the parts are
+/// reported as failed without running.
+///
+/// The generated `__solve` function runs all parts. It is called by the binary's `main` function and
+/// by the runner binary, which registers every solution in `src/bin` to run them for `all` and `time`.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +75,62 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr, 1) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_one, 1]);
+    };
+    ($day:expr, parse = $parse:expr, 2) => {
+        $crate::solution!(@impl $day, parse = $parse, [part_two, 2]);
+    };
+
+    (@impl $day:expr, parse = $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day);
+
+        // Not part of the public API
+        #[doc(hidden)]
+        pub fn __solve(
+            puzzle: $crate::template::PuzzleId,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::runner::PartRecord> {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file("inputs", puzzle);
+            let (parsed, parse_record) = run_parse($parse, &input, puzzle, options);
+            let mut records = vec![parse_record];
+            match parsed {
+                Some(parsed) => { $( records.push(run_part($func, &parsed, puzzle, $part, options)); )* }
+                None => { $( records.push(skip_part(puzzle, $part, options)); )* }
+            }
+            records
+        }
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day);
+
+        // Not part of the public API
+        #[doc(hidden)]
+        pub fn __solve(
+            puzzle: $crate::template::PuzzleId,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::runner::PartRecord> {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file("inputs", puzzle);
+            vec![$( run_part($func, &input, puzzle, $part, options), )*]
+        }
+    };
+
+    (@common $day:expr) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        /// The current puzzle.
+        pub const PUZZLE: $crate::template::PuzzleId =
+            $crate::template::PuzzleId::from_bin_name(env!("CARGO_BIN_NAME"), DAY);
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            __solve(PUZZLE, &$crate::template::runner::RunOptions::from_args());
         }
     };
 }
@@ -0,0 +1,156 @@
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::template::{Day, Year};
+
+/// Identifies the puzzle of a day in a specific event.
+///
+/// Puzzles of the event configured with `AOC_YEAR` do not carry an explicit year.
+/// They use the plain `DD` naming for binaries (`src/bin/05.rs`) and data files (`data/inputs/05.txt`).
+/// Puzzles of other events are prefixed with their year (`src/bin/2022-05.rs`, `data/2022/inputs/05.txt`).
+///
+/// # Display
+/// This value displays as the name of the puzzle's solution binary.
+///
+/// ```
+/// # use advent_of_code::{day, year, PuzzleId};
+/// let puzzle = PuzzleId::new(Some(year!(2022)), day!(5));
+/// assert_eq!(puzzle.to_string(), "2022-05")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    year: Option<Year>,
+    day: Day,
+}
+
+impl PuzzleId {
+    /// Creates a [`PuzzleId`]. A year matching `AOC_YEAR` is dropped.
+    pub fn new(year: Option<Year>, day: Day) -> Self {
+        Self {
+            year: Year::unless_configured(year),
+            day,
+        }
+    }
+
+    /// Creates a [`PuzzleId`] for a solution binary.
+    /// Binaries named `YYYY-DD` belong to the year `YYYY`, all others to the configured year.
+    pub const fn from_bin_name(name: &str, day: Day) -> Self {
+        let bytes = name.as_bytes();
+
+        if bytes.len() != 7 || bytes[4] != b'-' {
+            return Self { year: None, day };
+        }
+
+        let mut year: u16 = 0;
+        let mut i = 0;
+
+        while i < 4 {
+            if !bytes[i].is_ascii_digit() {
+                return Self { year: None, day };
+            }
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        if year < 2015 {
+            return Self { year: None, day };
+        }
+
+        Self {
+            year: Some(Year::__new_unchecked(year)),
+            day,
+        }
+    }
+
+    /// The day of the puzzle.
+    pub fn day(self) -> Day {
+        self.day
+    }
+
+    /// The explicit year of the puzzle, [`None`] for the configured year.
+    pub fn year(self) -> Option<Year> {
+        self.year
+    }
+
+    /// The year of the puzzle, falling back to the configured year.
+    pub fn resolve_year(self) -> Option<Year> {
+        self.year.or_else(Year::from_env)
+    }
+
+    /// Path to a file for this puzzle in a `data` sub-folder, e.g. `data/2022/inputs/05.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> PathBuf {
+        data_dir(self.year)
+            .join(folder)
+            .join(format!("{}.{extension}", self.day))
+    }
+
+    /// Path to the solution binary for this puzzle.
+    pub fn bin_path(self) -> PathBuf {
+        PathBuf::from("src").join("bin").join(format!("{self}.rs"))
+    }
+}
+
+impl From<Day> for PuzzleId {
+    fn from(day: Day) -> Self {
+        Self { year: None, day }
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year}-{}", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/// The `data` folder of an event: `data/` for the configured year, `data/YYYY/` for others.
+pub fn data_dir(year: Option<Year>) -> PathBuf {
+    let dir = PathBuf::from("data");
+    match year {
+        Some(year) => dir.join(year.to_string()),
+        None => dir,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+    use std::path::PathBuf;
+
+    #[test]
+    fn parses_bin_names() {
+        let puzzle = PuzzleId::from_bin_name("2022-05", day!(5));
+        assert_eq!(puzzle.year(), Some(year!(2022)));
+        assert_eq!(puzzle.to_string(), "2022-05");
+
+        assert_eq!(PuzzleId::from_bin_name("05", day!(5)).year(), None);
+        assert_eq!(PuzzleId::from_bin_name("1999-05", day!(5)).year(), None);
+        assert_eq!(PuzzleId::from_bin_name("20x2-05", day!(5)).year(), None);
+        assert_eq!(
+            PuzzleId::from_bin_name("advent_of_code", day!(5)).year(),
+            None
+        );
+    }
+
+    #[test]
+    fn builds_data_paths() {
+        let puzzle = PuzzleId::from_bin_name("2022-05", day!(5));
+        assert_eq!(
+            puzzle.data_path("inputs", "txt"),
+            PathBuf::from("data/2022/inputs/05.txt")
+        );
+        assert_eq!(puzzle.bin_path(), PathBuf::from("src/bin/2022-05.rs"));
+
+        let puzzle = PuzzleId::from(day!(5));
+        assert_eq!(
+            puzzle.data_path("puzzles", "md"),
+            PathBuf::from("data/puzzles/05.md")
+        );
+        assert_eq!(puzzle.bin_path(), PathBuf::from("src/bin/05.rs"));
+    }
+}
@@ -65,13 +65,21 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
+        // parts that did not finish before a day timed out are marked as such.
+        let missing = if timing.timed_out { "timed out" } else { "-" };
+
         lines.push(format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.part_1.unwrap_or_else(|| missing.into()),
+            timing.part_2.unwrap_or_else(|| missing.into())
         ));
+
+        // the parse step of a solution gets its own row below the parts.
+        if let Some(parse) = timing.parse {
+            lines.push(format!("| ↳ Parse | `{parse}` | |"));
+        }
     }
 
     lines.push(String::new());
@@ -110,18 +118,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -180,4 +191,35 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_parse_step() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+
+        let expected = [
+            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
+            "| ↳ Parse | `5ms` | |",
+            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+        ]
+        .join("\n");
+        assert!(s.contains("| Day | Part 1 | Part 2 |"));
+        assert!(s.contains(&expected));
+    }
+
+    #[test]
+    fn format_timed_out_days() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].timed_out = true;
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40ms` | `timed out` |"));
+    }
 }
@@ -0,0 +1,66 @@
+/// Solutions that are compiled into the runner binary, so that all days can be run from a single build.
+use crate::template::runner::{PartRecord, RunOptions};
+use crate::template::PuzzleId;
+
+/// Runs all parts of a solution. Generated by [`solution!`](crate::solution) as `__solve`.
+pub type SolveFn = fn(PuzzleId, &RunOptions) -> Vec<PartRecord>;
+
+/// A solution binary that is registered with the runner binary.
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub solve: SolveFn,
+}
+
+/// The solutions known to the runner binary.
+/// The runner's registry is generated by `build.rs` from the files in `src/bin`.
+#[derive(Default)]
+pub struct Registry {
+    solutions: Vec<Solution>,
+}
+
+impl Registry {
+    pub fn new(solutions: Vec<Solution>) -> Self {
+        Self { solutions }
+    }
+
+    /// Returns the solution of a puzzle if it is registered.
+    pub fn get(&self, puzzle: PuzzleId) -> Option<&Solution> {
+        self.solutions.iter().find(|solution| {
+            solution.puzzle.day() == puzzle.day()
+                && solution.puzzle.resolve_year() == puzzle.resolve_year()
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Registry, Solution};
+    use crate::template::runner::{PartRecord, RunOptions};
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+
+    fn solve(_: PuzzleId, _: &RunOptions) -> Vec<PartRecord> {
+        vec![]
+    }
+
+    #[test]
+    fn finds_registered_solutions() {
+        let registry = Registry::new(vec![
+            Solution {
+                puzzle: PuzzleId::from_bin_name("05", day!(5)),
+                solve,
+            },
+            Solution {
+                puzzle: PuzzleId::from_bin_name("2015-05", day!(5)),
+                solve,
+            },
+        ]);
+
+        let found = registry.get(PuzzleId::new(Some(year!(2015)), day!(5)));
+        assert_eq!(found.map(|s| s.puzzle.year()), Some(Some(year!(2015))));
+        assert!(registry.get(PuzzleId::from(day!(5))).is_some());
+        assert!(registry.get(PuzzleId::from(day!(6))).is_none());
+    }
+}
@@ -1,40 +1,160 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::HashSet,
+    env, fs,
+    io::{stderr, stdout, Read, Write},
+    num::NonZeroUsize,
+    path::Path,
+    process::{self, Command, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    expected::{ExpectedAnswers, Status},
+    registry::Registry,
+    runner::{parse_records, PartRecord, RunOptions, PARSE},
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Wall-clock time a single day may take before it is killed.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Summary of running a set of solutions.
+pub struct RunSummary {
+    pub timings: Option<Timings>,
+    /// Number of parts whose answer changed or went missing compared to the expected answers.
+    pub regressions: usize,
+    /// Number of days whose solution could not be run, exited with an error or timed out.
+    pub failures: usize,
+}
+
+/// Outcome of running the solution of one day.
+enum DayOutcome {
+    /// The solution exited, the records of all parts that ran are collected.
+    Finished(Vec<PartRecord>),
+    /// The solution could not be started or exited with an error, e.g. because its input is missing.
+    Failed {
+        records: Vec<PartRecord>,
+        reason: String,
+    },
+    /// The solution was killed after exceeding the timeout, with the records of the parts that finished before.
+    TimedOut(Vec<PartRecord>),
+}
+
+/// A day that ran in a child process. Its output is only kept if it was captured, see [`run_day`].
+struct DayRun {
+    outcome: DayOutcome,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
 
-    let mut need_space = false;
+/// Run the registered solutions of a set of days.
+/// Every day runs in a child process of this binary, which is killed if the day exceeds `timeout` (60 seconds by default).
+///
+/// Untimed runs execute up to `jobs` days at once, one per available CPU by default. The output of every day is
+/// buffered and printed in day order. Timed runs are always sequential, so concurrent days do not disturb benchmarks.
+pub fn run_multi(
+    registry: &Registry,
+    days_to_run: &HashSet<Day>,
+    year: Option<Year>,
+    options: &RunOptions,
+    expected: Option<&ExpectedAnswers>,
+    timeout: Option<Duration>,
+    jobs: Option<usize>,
+) -> RunSummary {
+    let timeout = timeout.unwrap_or(DEFAULT_TIMEOUT);
+    let jobs = if options.time {
+        1
+    } else {
+        jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, NonZeroUsize::get))
+    };
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<PuzzleId> = all_days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
+        .map(|day| PuzzleId::new(year, day))
+        .collect();
+
+    let mut report = Report {
+        expected,
+        timeout,
+        timings: Vec::with_capacity(puzzles.len()),
+        regressions: 0,
+        failures: 0,
+        need_space: false,
+    };
+
+    if jobs <= 1 {
+        for puzzle in puzzles {
+            report.print_header(puzzle);
+            // days run one after another with their output going straight to the console.
+            let run = registry
+                .get(puzzle)
+                .map(|_| run_day(puzzle, options, timeout, false));
+            report.add(puzzle, run);
+        }
+    } else {
+        let next = AtomicUsize::new(0);
+        let (sender, receiver) = mpsc::channel();
+
+        thread::scope(|scope| {
+            for _ in 0..jobs.min(puzzles.len()) {
+                let sender = sender.clone();
+                let (next, puzzles) = (&next, &puzzles);
+
+                scope.spawn(move || loop {
+                    let index = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(puzzle) = puzzles.get(index).copied() else {
+                        break;
+                    };
+                    let run = registry
+                        .get(puzzle)
+                        .map(|_| run_day(puzzle, options, timeout, true));
+                    if sender.send((index, run)).is_err() {
+                        break;
+                    }
+                });
             }
-            need_space = true;
+            drop(sender);
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+            // days finish in any order, print every day once all days before it are printed.
+            let mut finished: Vec<Option<Option<DayRun>>> = puzzles.iter().map(|_| None).collect();
+            let mut printed = 0;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            for (index, run) in receiver {
+                finished[index] = Some(run);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+                while let Some(run) = finished.get_mut(printed).and_then(Option::take) {
+                    report.print_header(puzzles[printed]);
+                    report.add(puzzles[printed], run);
+                    printed += 1;
+                }
             }
         });
+    }
 
-    if is_timed {
+    let Report {
+        timings,
+        regressions,
+        failures,
+        ..
+    } = report;
+
+    if expected.is_some() {
+        println!("\n{ANSI_BOLD}Regressions:{ANSI_RESET} {regressions}");
+    }
+    if failures > 0 {
+        println!("{ANSI_BOLD}Failed days:{ANSI_RESET} {failures}");
+    }
+
+    let timings = if options.time {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
@@ -43,215 +163,368 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
         Some(timings)
     } else {
         None
+    };
+
+    RunSummary {
+        timings,
+        regressions,
+        failures,
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+/// Prints the results of the days of a run and collects their timings and regressions.
+struct Report<'a> {
+    expected: Option<&'a ExpectedAnswers>,
+    timeout: Duration,
+    timings: Vec<Timing>,
+    regressions: usize,
+    failures: usize,
+    need_space: bool,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl Report<'_> {
+    fn print_header(&mut self, puzzle: PuzzleId) {
+        if self.need_space {
+            println!();
+        }
+        self.need_space = true;
+
+        println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}");
+        println!("------");
     }
-}
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
+    /// Print the outcome of a day, which is `None` for days that have not been scaffolded yet.
+    fn add(&mut self, puzzle: PuzzleId, run: Option<DayRun>) {
+        let Some(run) = run else {
+            println!("Not solved.");
+            return;
+        };
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+        // captured output is empty if it was passed through to the console.
+        let _ = stdout().write_all(&run.stdout);
+        let _ = stdout().flush();
+        let _ = stderr().write_all(&run.stderr);
+
+        let (records, timed_out) = match run.outcome {
+            DayOutcome::Finished(records) => (records, false),
+            DayOutcome::Failed { records, reason } => {
+                println!("\r✖ {ANSI_BOLD}failed{ANSI_RESET}: {reason}             ");
+                self.failures += 1;
+                (records, false)
+            }
+            DayOutcome::TimedOut(records) => {
+                println!(
+                    "\r✖ {ANSI_BOLD}timed out{ANSI_RESET} after {:.1?}             ",
+                    self.timeout
+                );
+                self.failures += 1;
+                (records, true)
+            }
+        };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+        let day = puzzle.day();
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if !records.is_empty() || timed_out {
+            self.timings.push(Timing {
+                timed_out,
+                ..get_timing(day, &records)
+            });
+        }
 
-        if is_release {
-            args.push("--release");
+        // days that stopped early are checked as well, their missing parts fail.
+        if let Some(expected) = self.expected {
+            for (part, status) in (1..).zip(check_answers(expected, day, &records)) {
+                if status.is_regression() {
+                    self.regressions += 1;
+                }
+                println!("Answer {part}: {status}");
+            }
         }
+    }
+}
+
+/// Compare the answers of both parts of a day with the expected answers. Parts without a record have no answer.
+fn check_answers(expected: &ExpectedAnswers, day: Day, records: &[PartRecord]) -> [Status; 2] {
+    [1, 2].map(|part| {
+        let answer = records
+            .iter()
+            .find(|record| record.part == part)
+            .and_then(|record| record.answer.as_deref());
+
+        expected.check(day, part, answer)
+    })
+}
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+/// Run the solution of a day in a child process of this binary, see the hidden `run` command.
+/// The child appends its records to a temporary file, and is killed once it exceeds `timeout`.
+/// With `capture`, its output is collected instead of being passed through to the console.
+fn run_day(puzzle: PuzzleId, options: &RunOptions, timeout: Duration, capture: bool) -> DayRun {
+    let records_path =
+        env::temp_dir().join(format!("advent_of_code-{}-{puzzle}.jsonl", process::id()));
+    let _ = fs::remove_file(&records_path);
+
+    let spawned = env::current_exe().and_then(|exe| {
+        let mut command = Command::new(exe);
+        command.args(child_args(puzzle, options, &records_path));
+        if capture {
+            command.stdout(Stdio::piped()).stderr(Stdio::piped());
         }
+        command.spawn()
+    });
+
+    let mut child = match spawned {
+        Ok(child) => child,
+        Err(e) => {
+            return DayRun {
+                outcome: DayOutcome::Failed {
+                    records: vec![],
+                    reason: format!("could not run day {puzzle}: {e}"),
+                },
+                stdout: vec![],
+                stderr: vec![],
+            };
+        }
+    };
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    // pipes need to be drained while the child runs, it blocks once their buffer is full.
+    let stdout = child.stdout.take().map(read_to_end);
+    let stderr = child.stderr.take().map(read_to_end);
+
+    let started = Instant::now();
+    let mut timed_out = false;
+    let exited = loop {
+        match child.try_wait() {
+            Ok(Some(status)) if status.success() => break Ok(()),
+            Ok(Some(status)) => break Err(format!("solution exited with {status}")),
+            Ok(None) if started.elapsed() >= timeout => {
+                let _ = child.kill();
+                let _ = child.wait();
+                timed_out = true;
+                break Ok(());
+            }
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Err(e) => break Err(format!("could not wait for day {puzzle}: {e}")),
+        }
+    };
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    let records = fs::read_to_string(&records_path)
+        .ok()
+        .map(|output| {
+            parse_records(&output).unwrap_or_else(|e| {
+                eprintln!("Failed to read records of day {puzzle}: {e}");
+                vec![]
+            })
+        })
+        .unwrap_or_default();
+    let _ = fs::remove_file(&records_path);
+
+    let joined = |reader: Option<thread::JoinHandle<Vec<u8>>>| {
+        reader
+            .and_then(|reader| reader.join().ok())
+            .unwrap_or_default()
+    };
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    DayRun {
+        outcome: match exited {
+            _ if timed_out => DayOutcome::TimedOut(records),
+            Ok(()) => DayOutcome::Finished(records),
+            Err(reason) => DayOutcome::Failed { records, reason },
+        },
+        stdout: joined(stdout),
+        stderr: joined(stderr),
+    }
+}
 
+/// Read a pipe of a child process to its end on a separate thread.
+pub(crate) fn read_to_end(mut pipe: impl Read + Send + 'static) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
         let mut output = vec![];
+        let _ = pipe.read_to_end(&mut output);
+        output
+    })
+}
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
-
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+/// Arguments of the hidden `run` command that runs a single day with `options`.
+fn child_args(puzzle: PuzzleId, options: &RunOptions, records_path: &Path) -> Vec<String> {
+    let mut args = vec!["run".to_string()];
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    if let Some(year) = puzzle.year() {
+        args.push(year.to_string());
+    }
+    args.push(puzzle.day().to_string());
+    args.extend(["--records".into(), records_path.display().to_string()]);
 
-        Ok(output)
+    if options.time {
+        args.push("--time".into());
+    }
+    if options.compare {
+        args.push("--compare".into());
+    }
+    if let Some(budget) = options.budget {
+        args.extend(["--budget".into(), budget.as_millis().to_string()]);
+    }
+    if let Some(iterations) = options.iterations {
+        args.extend(["--iterations".into(), iterations.to_string()]);
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+    args
+}
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+/// Collect the timings of the benched parts from the records of a day.
+fn get_timing(day: Day, records: &[PartRecord]) -> Timing {
+    let mut timing = Timing {
+        day,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        parse_stats: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        total_nanos: 0_f64,
+        timed_out: false,
+    };
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    for record in records {
+        // parts without an answer or that were only run once are not benched.
+        if (record.part != PARSE && record.answer.is_none()) || record.samples <= 1 {
+            continue;
+        }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        let duration = Some(format!("{:.1?}", record.duration));
 
-                timings.total_nanos += nanos;
-            });
+        match record.part {
+            PARSE => (timing.parse, timing.parse_stats) = (duration, record.stats),
+            1 => (timing.part_1, timing.part_1_stats) = (duration, record.stats),
+            2 => (timing.part_2, timing.part_2_stats) = (duration, record.stats),
+            _ => continue,
+        }
 
-        timings
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = record.duration.as_nanos() as f64;
+        timing.total_nanos += nanos;
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    timing
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_answers, child_args, get_timing};
+    use crate::{
+        day,
+        template::{
+            expected::{ExpectedAnswers, Status},
+            runner::{PartRecord, RunOptions},
+            PuzzleId,
+        },
+        year,
+    };
+    use std::{path::Path, time::Duration};
+
+    fn record(part: u8, answer: Option<&str>, nanos: u64, samples: u128) -> PartRecord {
+        PartRecord {
+            day: day!(1),
+            part,
+            answer: answer.map(String::from),
+            error: None,
+            duration: Duration::from_nanos(nanos),
+            samples,
+            stats: None,
+        }
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    #[test]
+    fn collects_timings_of_benched_parts() {
+        let res = get_timing(
+            day!(1),
+            &[
+                record(1, Some("0"), 74_130, 100_000),
+                record(2, Some("10"), 74_130_000, 99),
+            ],
+        );
+        assert_eq!(res.total_nanos, 74_204_130_f64);
+        assert_eq!(res.part_1.unwrap(), "74.1µs");
+        assert_eq!(res.part_2.unwrap(), "74.1ms");
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    #[test]
+    fn collects_timing_of_parse_step() {
+        let res = get_timing(
+            day!(1),
+            &[record(0, None, 1_000, 10), record(1, Some("1"), 2_000, 10)],
+        );
+        assert_eq!(res.parse.unwrap(), "1.0µs");
+        assert_eq!(res.part_1.unwrap(), "2.0µs");
+        assert_eq!(res.total_nanos, 3_000_f64);
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    #[test]
+    fn skips_missing_and_unbenched_parts() {
+        let res = get_timing(
+            day!(1),
+            &[record(1, None, 100, 10), record(2, Some("10"), 100, 1)],
+        );
+        assert_eq!(res.total_nanos, 0_f64);
+        assert!(res.part_1.is_none());
+        assert!(res.part_2.is_none());
+    }
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn fails_parts_without_records() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "0", "part_2": "10" }] }"#;
+        let expected = ExpectedAnswers::try_from(json.to_string()).unwrap();
+
+        assert_eq!(
+            check_answers(&expected, day!(1), &[record(1, Some("0"), 100, 1)]),
+            [
+                Status::Pass,
+                Status::Fail {
+                    expected: "10".into()
+                }
+            ]
+        );
+        assert!(check_answers(&expected, day!(1), &[])
+            .iter()
+            .all(Status::is_regression));
+        assert_eq!(
+            check_answers(&expected, day!(2), &[]),
+            [Status::Unchecked, Status::Unchecked]
+        );
+    }
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn passes_options_to_child() {
+        let options = RunOptions {
+            time: true,
+            budget: Some(Duration::from_millis(500)),
+            ..RunOptions::default()
+        };
+        let args = child_args(
+            PuzzleId::new(Some(year!(2015)), day!(5)),
+            &options,
+            Path::new("records.jsonl"),
+        );
+        assert_eq!(
+            args,
+            [
+                "run",
+                "2015",
+                "05",
+                "--records",
+                "records.jsonl",
+                "--time",
+                "--budget",
+                "500"
+            ]
+        );
+
+        let args = child_args(
+            PuzzleId::from(day!(5)),
+            &RunOptions::default(),
+            Path::new("r"),
+        );
+        assert_eq!(args, ["run", "05", "--records", "r"]);
     }
 }
@@ -1,35 +1,401 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::collections::HashMap;
 use std::fmt::Display;
+use std::fs::OpenOptions;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
+use tinyjson::JsonValue;
 
+use crate::template::answers::{self, Answer, Answers};
+use crate::template::aoc_client::AocClient;
+use crate::template::history::Delta;
+use crate::template::stats::Stats;
+use crate::template::timings::Timings;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+const DEFAULT_BUDGET: Duration = Duration::from_secs(1);
+
+/// Part number of the parse step in [`PartRecord`]s.
+pub const PARSE: u8 = 0;
+
+/// How the results of a part are reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable console output.
+    #[default]
+    Text,
+    /// One JSON record per part, see [`PartRecord`].
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{s}`, expecting `text` or `json`.")),
+        }
+    }
+}
+
+/// Options for running the parts of a solution.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench each part after its first run.
+    pub time: bool,
+    /// Submit the answer of this part.
+    pub submit: Option<u8>,
+    pub format: Format,
+    /// File that JSON records are appended to, in either format. Required in JSON format.
+    pub records: Option<PathBuf>,
+    /// Time budget for benching a part. Defaults to 1 second.
+    pub budget: Option<Duration>,
+    /// Fixed number of samples to bench a part with, overrides the budget.
+    pub iterations: Option<u128>,
+    /// Print the change against the stored timing of benched parts.
+    pub compare: bool,
+}
+
+impl RunOptions {
+    /// Parse the options passed to a solution binary by `solve` and `time`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value_of = |name: &str| {
+            let index = args.iter().position(|x| x == name)?;
+            let Some(value) = args.get(index + 1) else {
+                eprintln!("Missing value for `{name}`.");
+                process::exit(1);
+            };
+            Some(value)
+        };
+
+        let submit = value_of("--submit").map(|value| {
+            let Ok(part) = value.parse::<u8>() else {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                process::exit(1);
+            };
+            part
+        });
+
+        let format = value_of("--format").map_or(Format::Text, |value| {
+            value.parse().unwrap_or_else(|e| {
+                eprintln!("{e}");
+                process::exit(1);
+            })
+        });
+
+        let budget = value_of("--budget").map(|value| {
+            let Ok(millis) = value.parse::<u64>() else {
+                eprintln!("Unexpected command-line input. Format: --budget <milliseconds>");
+                process::exit(1);
+            };
+            Duration::from_millis(millis)
+        });
+
+        let iterations = value_of("--iterations").map(|value| {
+            let Some(iterations) = value.parse::<u128>().ok().filter(|x| *x > 0) else {
+                eprintln!("Unexpected command-line input. Format: --iterations <samples>");
+                process::exit(1);
+            };
+            iterations
+        });
+
+        // records are not printed to stdout, where they would be mixed with the output of the solution.
+        let records = value_of("--records").map(PathBuf::from);
+        if format == Format::Json && records.is_none() {
+            eprintln!("Missing `--records <path>` to write the JSON records to.");
+            process::exit(1);
+        }
+
+        let time = args.iter().any(|x| x == "--time");
+
+        Self {
+            time,
+            submit,
+            format,
+            records,
+            budget,
+            iterations,
+            compare: time,
+        }
+    }
+}
+
+/// Return types of solution parts. Parts return [`Option`] if they may not produce an answer,
+/// or [`Result`] if they can fail with an error that is printed in place of the answer.
+pub trait PartResult {
+    /// The answer of the part, or the message of the error it failed with.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+/// The outcome of running one part, or the parse step, of a solution.
+/// Written as a JSON record per part to the file passed with `--records`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// The error the part returned, or the message it panicked with.
+    pub error: Option<String>,
+    /// The median when benched, the duration of the single run otherwise.
+    pub duration: Duration,
+    pub samples: u128,
+    /// Statistics of the benchmark, if the part was benched.
+    pub stats: Option<Stats>,
+}
+
+/// Run one part of a solution. Parts that return an error or panic are reported as failed and are
+/// neither benched nor submitted. A panic does not propagate, so the remaining parts still run.
+pub fn run_part<I: Clone, R: PartResult>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> PartRecord {
     let part_str = format!("Part {part}");
+    let is_text = options.format == Format::Text;
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(&func, input, options, |result| {
+            let answer = result.to_answer();
+            let bench = options.time && answer.is_ok();
+            if is_text {
+                print_result(&answer, &part_str, "");
+                if bench {
+                    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                }
+                let _ = stdout().flush();
+            }
+            bench
+        })
+    }));
+
+    let record = match outcome {
+        Ok((result, base_time, benchmark)) => {
+            let answer = result.to_answer();
+            let (duration, duration_str) =
+                finish_timing(puzzle, part, options, base_time, &benchmark);
+
+            if is_text {
+                print_result(&answer, &part_str, &duration_str);
+            }
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+            let (answer, error) = match answer {
+                Ok(answer) => (answer, None),
+                Err(error) => (None, Some(error)),
+            };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+            PartRecord {
+                day: puzzle.day(),
+                part,
+                answer,
+                error,
+                duration,
+                samples: benchmark.as_ref().map_or(1, |b| b.samples),
+                stats: benchmark.map(|b| b.stats),
+            }
+        }
+        Err(payload) => {
+            let message = panic_message(payload.as_ref());
+
+            if is_text {
+                println!("\r{part_str}: ✖ {ANSI_BOLD}panicked{ANSI_RESET}: {message}");
+            }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+            failed_record(puzzle, part, format!("panicked: {message}"))
+        }
+    };
+
+    write_record(&record, options);
+
+    if let Some(answer) = &record.answer {
+        if options.submit == Some(part) {
+            submit_result(answer.clone(), puzzle, part);
+        }
     }
+
+    record
+}
+
+/// Report a part that could not run because the parse step failed.
+pub fn skip_part(puzzle: PuzzleId, part: u8, options: &RunOptions) -> PartRecord {
+    if options.format == Format::Text {
+        println!("Part {part}: ✖ {ANSI_BOLD}parse failed{ANSI_RESET}");
+    }
+
+    let record = failed_record(puzzle, part, "parse failed".into());
+    write_record(&record, options);
+    record
+}
+
+/// The record of a part, or the parse step, that did not produce a result.
+fn failed_record(puzzle: PuzzleId, part: u8, error: String) -> PartRecord {
+    PartRecord {
+        day: puzzle.day(),
+        part,
+        answer: None,
+        error: Some(error),
+        duration: Duration::ZERO,
+        samples: 0,
+        stats: None,
+    }
+}
+
+/// The message a panic was raised with, if it has one.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "no message".into())
+}
+
+/// Run the parse step of a solution. It is timed like a part and reported as part [`PARSE`].
+/// A panic does not propagate, the parsed value is [`None`] then and the parts are skipped, see [`skip_part`].
+pub fn run_parse<'a, T>(
+    func: impl Fn(&'a str) -> T,
+    input: &'a str,
+    puzzle: PuzzleId,
+    options: &RunOptions,
+) -> (Option<T>, PartRecord) {
+    let is_text = options.format == Format::Text;
+
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(&func, input, options, |_| {
+            if is_text {
+                print!("Parse: ✔");
+                if options.time {
+                    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                }
+                let _ = stdout().flush();
+            }
+            options.time
+        })
+    }));
+
+    let (result, record) = match outcome {
+        Ok((result, base_time, benchmark)) => {
+            let (duration, duration_str) =
+                finish_timing(puzzle, PARSE, options, base_time, &benchmark);
+
+            if is_text {
+                println!("\rParse: ✔{duration_str}");
+            }
+
+            let record = PartRecord {
+                day: puzzle.day(),
+                part: PARSE,
+                answer: None,
+                error: None,
+                duration,
+                samples: benchmark.as_ref().map_or(1, |b| b.samples),
+                stats: benchmark.map(|b| b.stats),
+            };
+            (Some(result), record)
+        }
+        Err(payload) => {
+            let message = panic_message(payload.as_ref());
+
+            if is_text {
+                println!("\rParse: ✖ {ANSI_BOLD}parse failed{ANSI_RESET}: {message}");
+            }
+
+            (
+                None,
+                failed_record(puzzle, PARSE, format!("panicked: {message}")),
+            )
+        }
+    };
+
+    write_record(&record, options);
+
+    (result, record)
+}
+
+/// Returns the duration to report for a part and its formatted form.
+/// Benched parts report the median, single runs their only sample.
+fn finish_timing(
+    puzzle: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+    base_time: Duration,
+    benchmark: &Option<Benchmark>,
+) -> (Duration, String) {
+    let duration = benchmark.as_ref().map_or(base_time, |b| b.stats.median);
+
+    let baseline = options
+        .compare
+        .then(|| Timings::read_from_file(puzzle.year()).part_stats(puzzle.day(), part))
+        .flatten()
+        .map(|stats| Delta {
+            day: puzzle.day(),
+            part,
+            previous: stats.median,
+            current: duration,
+        });
+
+    let duration_str = format_duration(&duration, benchmark.as_ref(), baseline.as_ref());
+    (duration, duration_str)
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Append a JSON record to the records file, if one is set.
+fn write_record(record: &PartRecord, options: &RunOptions) {
+    let Some(path) = options.records.as_deref() else {
+        return;
+    };
+
+    let line = match JsonValue::from(record).stringify() {
+        Ok(line) => line,
+        Err(e) => {
+            eprintln!("Failed to serialize record: {e}");
+            return;
+        }
+    };
+
+    let written = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{line}"));
+
+    if let Err(e) = written {
+        eprintln!("Failed to write record to \"{}\": {e}", path.display());
+    }
+}
+
+/// Run a solution part once and pass its result to `hook`. If the hook returns `true`, the function is benched
+/// afterwards, see [`bench`]. Returns the result, the duration of the first run and the benchmark.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    options: &RunOptions,
+    hook: impl Fn(&T) -> bool,
+) -> (T, Duration, Option<Benchmark>) {
     let timer = Instant::now();
     let result = {
         let input = input.clone();
@@ -41,65 +407,86 @@ fn run_timed<I: Clone, T>(
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
+    let benchmark = hook(&result)
+        .then(|| bench(func, input, &base_time, options))
+        .and_then(|benchmark| {
+            benchmark
+                .map_err(|e| eprintln!("\nFailed to bench: {e}"))
+                .ok()
+        });
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
+    (result, base_time, benchmark)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+struct Benchmark {
+    stats: Stats,
+    samples: u128,
+}
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+/// Bench a solution part:
+///  1. the number of samples is taken from `options.iterations`, or derived from the time budget (1 second by default)
+///     and the duration of the first run, clamped to 10 - 10.000 samples.
+///  2. a tenth of the samples is run up front as warmup and discarded.
+///
+/// Fails if there are no samples to compute statistics from.
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    options: &RunOptions,
+) -> Result<Benchmark, String> {
+    let budget = options.budget.unwrap_or(DEFAULT_BUDGET);
 
-    let mut timers: Vec<Duration> = vec![];
+    let bench_iterations = options.iterations.unwrap_or_else(|| {
+        (budget.as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000)
+    });
 
-    for _ in 0..bench_iterations {
+    let run = || {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+        timer.elapsed()
+    };
+
+    for _ in 0..bench_iterations.div_ceil(10) {
+        run();
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
+    let timers: Vec<Duration> = (0..bench_iterations).map(|_| run()).collect();
+
+    let stats = Stats::from_samples(&timers).ok_or("needs at least one iteration.")?;
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Ok(Benchmark {
+        stats,
+        samples: bench_iterations,
+    })
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+fn format_duration(
+    duration: &Duration,
+    benchmark: Option<&Benchmark>,
+    baseline: Option<&Delta>,
+) -> String {
+    let delta = baseline.map_or(String::new(), |delta| {
+        format!(", Δ {:+.1}% vs {:.1?}", delta.percent(), delta.previous)
+    });
+
+    match benchmark {
+        None => format!(" ({duration:.1?}{delta})"),
+        Some(Benchmark { stats, samples }) => format!(
+            " ({duration:.1?} @ {samples} samples, min {:.1?}, p95 {:.1?}, σ {:.1?}{delta})",
+            stats.min, stats.p95, stats.stddev
+        ),
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(result: &Result<Option<String>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+        Ok(Some(result)) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -118,7 +505,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -126,44 +513,314 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(error) => {
+            let str = format!("{part}: ✖ {ANSI_BOLD}error{ANSI_RESET}: {error}{duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
+/// Try to submit one part of the solution if the answer is not known to be wrong from a previous submission.
+fn submit_result(answer: String, puzzle: PuzzleId, part: u8) {
+    if let Some(warning) = answers::suspicious(&answer) {
+        eprintln!("Warning: {warning}");
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let mut ledger = Answers::read_from_file(puzzle.year());
+
+    if let Err(rejection) = ledger.check(puzzle.day(), part, &answer) {
+        eprintln!("Refusing to submit `{answer}`: {rejection}");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let client = match AocClient::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("failed to submit: {e}");
+            process::exit(1);
+        }
+    };
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    println!("Submitting result...");
+
+    match client.submit(puzzle, part, &answer) {
+        Ok(submission) => {
+            println!("{}", submission.message);
+
+            if let Some(verdict) = submission.verdict {
+                ledger.record(Answer {
+                    day: puzzle.day(),
+                    part,
+                    answer,
+                    verdict,
+                });
+
+                if let Err(e) = ledger.store_file(puzzle.year()) {
+                    eprintln!("Failed to store answer: {e}");
+                }
+            }
+        }
+        Err(e) => {
+            eprintln!("failed to submit: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "nanos".into(),
+            JsonValue::Number(value.duration.as_nanos() as f64),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| [0.0, 1.0, 2.0].contains(*part))
+            .ok_or("Expected record.part to be 0 (parse), 1 or 2.")?;
+
+        let answer = match json.get("answer") {
+            Some(JsonValue::String(answer)) => Some(answer.clone()),
+            Some(JsonValue::Null) => None,
+            _ => return Err("Expected record.answer to be null or string.".into()),
+        };
+
+        // records written before parts could fail do not have an error.
+        let error = match json.get("error") {
+            Some(JsonValue::String(error)) => Some(error.clone()),
+            None | Some(JsonValue::Null) => None,
+            _ => return Err("Expected record.error to be null or string.".into()),
+        };
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected record.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected record.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None | Some(JsonValue::Null) => None,
+            Some(stats) => Some(Stats::try_from(stats)?),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartRecord {
+            day,
+            part: *part as u8,
+            answer,
+            error,
+            duration: Duration::from_nanos(*nanos as u64),
+            samples: *samples as u128,
+            stats,
+        })
+    }
+}
+
+/// Parse the JSON records written by a solution run with `--records`, one per line.
+pub fn parse_records(output: &str) -> Result<Vec<PartRecord>, String> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("not a valid JSON record."))?;
+            PartRecord::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        bench, parse_records, run_parse, run_part, skip_part, Format, PartRecord, PartResult,
+        RunOptions,
     };
+    use crate::{
+        day,
+        template::{stats::Stats, PuzzleId},
+    };
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_records() {
+        let records = [
+            PartRecord {
+                day: day!(1),
+                part: 1,
+                answer: Some("#.#\n.#.".into()),
+                error: None,
+                duration: Duration::from_nanos(74_130),
+                samples: 10_000,
+                stats: Stats::from_samples(&[Duration::from_nanos(74_130)]),
+            },
+            PartRecord {
+                day: day!(1),
+                part: 2,
+                answer: None,
+                error: Some("panicked: oops".into()),
+                duration: Duration::from_nanos(12),
+                samples: 1,
+                stats: None,
+            },
+        ];
 
-    if part_submit != part {
-        return None;
+        let output = records
+            .iter()
+            .map(|record| JsonValue::from(record).stringify().unwrap() + "\n")
+            .collect::<String>();
+
+        assert_eq!(output.lines().count(), 2);
+        assert_eq!(parse_records(&output).unwrap(), records);
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn rejects_malformed_records() {
+        assert!(parse_records("Part 1: 42 (1.0ms @ 10 samples)").is_err());
+        assert!(parse_records(r#"{ "day": "01", "part": 3 }"#).is_err());
+    }
+
+    #[test]
+    fn parses_records_without_error() {
+        let records =
+            parse_records(r#"{"day":"01","part":1,"answer":"42","nanos":19,"samples":1}"#).unwrap();
+        assert_eq!(records[0].answer.as_deref(), Some("42"));
+        assert_eq!(records[0].error, None);
+    }
+
+    #[test]
+    fn converts_part_results() {
+        assert_eq!(Some(42).to_answer(), Ok(Some("42".into())));
+        assert_eq!(None::<u32>.to_answer(), Ok(None));
+        assert_eq!(Ok::<_, String>("a\nb").to_answer(), Ok(Some("a\nb".into())));
+        assert_eq!(Err::<u32, _>("no loop").to_answer(), Err("no loop".into()));
+    }
+
+    #[test]
+    fn isolates_failing_parts() {
+        let puzzle = PuzzleId::new(None, day!(1));
+        let options = RunOptions::default();
+
+        let record = run_part(|_: &str| Err::<u32, _>("no loop"), "", puzzle, 1, &options);
+        assert_eq!(record.answer, None);
+        assert_eq!(record.error.as_deref(), Some("no loop"));
+
+        let record = run_part(
+            |input: &str| -> Option<usize> { panic!("unexpected `{input}`") },
+            "x",
+            puzzle,
+            2,
+            &options,
+        );
+        assert_eq!(record.answer, None);
+        assert_eq!(record.error.as_deref(), Some("panicked: unexpected `x`"));
+        assert_eq!(record.samples, 0);
+
+        let record = run_part(|input: &str| Some(input.len()), "abc", puzzle, 2, &options);
+        assert_eq!((record.answer.as_deref(), record.error), (Some("3"), None));
+    }
+
+    #[test]
+    fn isolates_failing_parse_steps() {
+        let puzzle = PuzzleId::new(None, day!(1));
+        let options = RunOptions::default();
+
+        let (parsed, record) = run_parse(
+            |input: &str| -> u32 { input.parse().expect("not a number") },
+            "x",
+            puzzle,
+            &options,
+        );
+        assert_eq!(parsed, None);
+        assert_eq!(record.part, 0);
+        assert!(record.error.unwrap().starts_with("panicked: not a number"));
+
+        let record = skip_part(puzzle, 2, &options);
+        assert_eq!((record.part, record.answer), (2, None));
+        assert_eq!(record.error.as_deref(), Some("parse failed"));
+
+        let (parsed, record) = run_parse(str::len, "abc", puzzle, &options);
+        assert_eq!((parsed, record.error), (Some(3), None));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn refuses_to_bench_without_samples() {
+        let options = RunOptions {
+            iterations: Some(0),
+            ..RunOptions::default()
+        };
+        assert!(bench(|x: u32| x + 1, 1, &Duration::ZERO, &options).is_err());
+
+        let options = RunOptions {
+            iterations: Some(3),
+            ..RunOptions::default()
+        };
+        let benchmark = bench(|x: u32| x + 1, 1, &Duration::ZERO, &options).unwrap();
+        assert_eq!(benchmark.samples, 3);
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
 }
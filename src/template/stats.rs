@@ -0,0 +1,166 @@
+/// Summary statistics of benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Statistics of the timings of a benched part.
+/// Samples outside of Tukey's fences (1.5 × the interquartile range) are rejected as outliers before computing them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Computes statistics for a set of samples. Returns [`None`] if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 25);
+        let q3 = percentile(&sorted, 75);
+        let fence = (q3 - q1) * 3 / 2;
+        let (low, high) = (q1.saturating_sub(fence), q3 + fence);
+
+        let kept: Vec<u128> = sorted
+            .iter()
+            .copied()
+            .filter(|x| (low..=high).contains(x))
+            .collect();
+
+        let len = kept.len() as u128;
+        let mean = kept.iter().sum::<u128>() / len;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = kept
+            .iter()
+            .map(|x| (*x as f64 - mean as f64).powi(2))
+            .sum::<f64>()
+            / kept.len() as f64;
+
+        Some(Self {
+            mean: from_nanos(mean),
+            min: from_nanos(kept[0]),
+            median: from_nanos(percentile(&kept, 50)),
+            p95: from_nanos(percentile(&kept, 95)),
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            stddev: from_nanos(variance.sqrt().round() as u128),
+            outliers: (sorted.len() - kept.len()) as u128,
+        })
+    }
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[u128], p: usize) -> u128 {
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1)]
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn from_nanos(nanos: u128) -> Duration {
+    Duration::from_nanos(nanos as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut insert = |key: &str, nanos: u128| {
+            map.insert(key.into(), JsonValue::Number(nanos as f64));
+        };
+
+        insert("mean", value.mean.as_nanos());
+        insert("min", value.min.as_nanos());
+        insert("median", value.median.as_nanos());
+        insert("p95", value.p95.as_nanos());
+        insert("stddev", value.stddev.as_nanos());
+        insert("outliers", value.outliers);
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u128)
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(Stats {
+            mean: from_nanos(get("mean")?),
+            min: from_nanos(get("min")?),
+            median: from_nanos(get("median")?),
+            p95: from_nanos(get("p95")?),
+            stddev: from_nanos(get("stddev")?),
+            outliers: get("outliers")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+    use tinyjson::JsonValue;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40, 50])).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        assert_eq!(stats.stddev, Duration::from_nanos(14));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 5000])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.p95, Duration::from_nanos(12));
+        assert_eq!(stats.mean, Duration::from_nanos(10));
+    }
+
+    #[test]
+    fn handles_single_and_missing_samples() {
+        let stats = Stats::from_samples(&nanos(&[7])).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(7));
+        assert_eq!(stats.stddev, Duration::ZERO);
+        assert!(Stats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40, 50])).unwrap();
+        let json = JsonValue::from(&stats);
+        assert_eq!(Stats::try_from(&json), Ok(stats));
+    }
+}
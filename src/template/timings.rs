@@ -1,17 +1,41 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, path::PathBuf, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{data_dir, runner::PARSE, stats::Stats, Day, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Timing of the parse step, for solutions that declare one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
     pub total_nanos: f64,
+    /// The day was killed after exceeding the timeout of the run.
+    pub timed_out: bool,
+}
+
+impl Default for Timing {
+    /// Day 1, without any timings.
+    fn default() -> Self {
+        Self {
+            day: crate::day!(1),
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            total_nanos: 0.0,
+            timed_out: false,
+        }
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,20 +46,24 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings to the JSON file of an event.
+    pub fn store_file(&self, year: Option<Year>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let path = get_file_path(year);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::File::create(path)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
+    /// Rehydrate timings from the JSON file of an event. If not present, returns empty timings.
+    pub fn read_from_file(year: Option<Year>) -> Self {
+        let Ok(s) = fs::read_to_string(get_file_path(year)) else {
+            return Timings::default();
+        };
 
-        match s {
+        match Timings::try_from(s) {
             Ok(timings) => timings,
             Err(e) => {
                 eprintln!("{e}");
@@ -58,7 +86,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.day);
         Timings { data }
     }
 
@@ -67,6 +95,17 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Returns the benchmark statistics of a part, if they were stored.
+    pub fn part_stats(&self, day: Day, part: u8) -> Option<Stats> {
+        let timing = self.data.iter().find(|t| t.day == day)?;
+        match part {
+            PARSE => timing.parse_stats,
+            1 => timing.part_1_stats,
+            2 => timing.part_2_stats,
+            _ => None,
+        }
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -74,6 +113,10 @@ impl Timings {
     }
 }
 
+fn get_file_path(year: Option<Year>) -> PathBuf {
+    data_dir(year).join(TIMINGS_FILE_NAME)
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -140,6 +183,24 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
+        if value.timed_out {
+            map.insert("timed_out".into(), JsonValue::Boolean(true));
+        }
+
+        for (key, stats) in [
+            ("parse_stats", &value.parse_stats),
+            ("part_1_stats", &value.part_1_stats),
+            ("part_2_stats", &value.part_2_stats),
+        ] {
+            if let Some(stats) = stats {
+                map.insert(key.into(), JsonValue::from(stats));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -168,16 +229,39 @@ impl TryFrom<&JsonValue> for Timing {
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
             .ok_or("Expected timing.part_2 to be null or string.")?;
 
+        let parse = match json.get("parse") {
+            None | Some(JsonValue::Null) => None,
+            Some(JsonValue::String(parse)) => Some(parse.clone()),
+            Some(_) => return Err("Expected timing.parse to be null or string.".into()),
+        };
+
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let timed_out = match json.get("timed_out") {
+            None | Some(JsonValue::Null) => false,
+            Some(JsonValue::Boolean(timed_out)) => *timed_out,
+            Some(_) => return Err("Expected timing.timed_out to be a boolean.".into()),
+        };
+
+        // timings stored before stats were recorded do not have them.
+        let get_stats = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(stats) => Stats::try_from(stats).map(Some),
+        };
+
         Ok(Timing {
             day,
+            parse,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats: get_stats("parse_stats")?,
+            part_1_stats: get_stats("part_1_stats")?,
+            part_2_stats: get_stats("part_2_stats")?,
             total_nanos,
+            timed_out,
         })
     }
 }
@@ -198,18 +282,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -228,6 +315,25 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_stats, None);
+        }
+
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean": 1000000, "min": 900000, "median": 1000000, "p95": 1200000, "stddev": 50000, "outliers": 2 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let stats = timings.data[0].part_1_stats.unwrap();
+            assert_eq!(stats.p95.as_nanos(), 1_200_000);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(timings.data[0].part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_timed_out_days() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "timed_out": true }, { "day": "02", "part_1": null, "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert!(timings.data[0].timed_out);
+            assert!(!timings.data[1].timed_out);
         }
 
         #[test]
@@ -289,10 +395,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -303,10 +410,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -317,10 +425,11 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -341,6 +450,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -361,6 +471,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -372,6 +483,36 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_timed_out_days_until_they_finish() {
+            let timed_out = Timing {
+                day: day!(2),
+                part_1: Some("1.0s".into()),
+                part_2: None,
+                total_nanos: 1_000_000_000_f64,
+                timed_out: true,
+                ..Default::default()
+            };
+
+            let merged = get_mock_timings().merge(&Timings {
+                data: vec![timed_out.clone()],
+            });
+            assert!(merged.data[1].timed_out);
+            assert!(!merged.data[0].timed_out);
+
+            let finished = Timing {
+                part_2: Some("2.0s".into()),
+                total_nanos: 3_000_000_000_f64,
+                timed_out: false,
+                ..timed_out
+            };
+            let merged = merged.merge(&Timings {
+                data: vec![finished],
+            });
+            assert!(merged.data.iter().all(|timing| !timing.timed_out));
+            assert_eq!(merged.data[1].part_2.as_deref(), Some("2.0s"));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();